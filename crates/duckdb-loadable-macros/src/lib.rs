@@ -1,10 +1,10 @@
 #![allow(clippy::redundant_clone)]
 use proc_macro2::{Ident, Span};
 
-use syn::{parse_macro_input, spanned::Spanned, Item};
+use syn::{ext::IdentExt, parse_macro_input, spanned::Spanned, Item};
 
 use proc_macro::TokenStream;
-use quote::quote_spanned;
+use quote::{quote, quote_spanned};
 
 use darling::{ast::NestedMeta, Error, FromMeta};
 
@@ -167,3 +167,232 @@ pub fn duckdb_entrypoint(_attr: TokenStream, item: TokenStream) -> TokenStream {
         _ => panic!("Only function items are allowed on duckdb_entrypoint"),
     }
 }
+
+/// Derives `duckdb::vtab::TableRow` for a struct with named fields.
+///
+/// Every field becomes a column with the same name, its type must implement `duckdb::vtab::TableColumn`.
+#[proc_macro_derive(TableRow)]
+pub fn derive_table_row(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new(ast.span(), "TableRow can only be derived for structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let columns = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let column_name = ident.to_string();
+        quote! {
+            (
+                #column_name.to_string(),
+                <#ty as ::duckdb::vtab::TableColumn>::logical_type(),
+            )
+        }
+    });
+
+    let writes = fields.iter().enumerate().map(|(idx, field)| {
+        let ident = field.ident.as_ref().unwrap();
+        quote! {
+            ::duckdb::vtab::TableColumn::write_to(self.#ident, &mut output.flat_vector(#idx), row);
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::duckdb::vtab::TableRow for #name #ty_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<(::std::string::String, ::duckdb::core::LogicalTypeHandle)> {
+                ::std::vec![#(#columns),*]
+            }
+
+            fn write_to(self, output: &mut ::duckdb::core::DataChunkHandle, row: usize) {
+                #(#writes)*
+            }
+        }
+    }
+    .into()
+}
+
+/// The `#[duckdb(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    default: bool,
+    flatten: bool,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("duckdb")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    attrs.rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    attrs.default = true;
+                } else if meta.path.is_ident("flatten") {
+                    attrs.flatten = true;
+                } else {
+                    return Err(meta.error("expected `rename`, `default` or `flatten`"));
+                }
+                Ok(())
+            })?;
+        }
+        if attrs.flatten && (attrs.rename.is_some() || attrs.default) {
+            return Err(syn::Error::new(
+                field.span(),
+                "`flatten` cannot be combined with `rename` or `default`",
+            ));
+        }
+        if field.ident.is_none() && (attrs.flatten || attrs.rename.is_some()) {
+            return Err(syn::Error::new(
+                field.span(),
+                "`flatten` and `rename` need a named field",
+            ));
+        }
+        Ok(attrs)
+    }
+}
+
+/// The fields of a struct, or an error for enums and unions.
+fn struct_fields<'a>(ast: &'a syn::DeriveInput, derive: &str) -> syn::Result<&'a syn::Fields> {
+    match &ast.data {
+        syn::Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new(
+            ast.span(),
+            format!("{derive} can only be derived for structs"),
+        )),
+    }
+}
+
+/// Derives `duckdb::FromRow` for a struct.
+///
+/// The fields of a struct with named fields are read from the columns of the same name, the fields of a tuple struct
+/// are read from the columns in order. Fields accept `#[duckdb(...)]` attributes:
+///
+/// - `rename = "column"` reads the field from the given column;
+/// - `default` uses `Default::default()` when the column does not exist;
+/// - `flatten` reads the field, itself a `FromRow`, from the same row.
+#[proc_macro_derive(FromRow, attributes(duckdb))]
+pub fn derive_from_row(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    match from_row(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn from_row(ast: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(ast, "FromRow")?;
+    let reads = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let attrs = FieldAttrs::parse(field)?;
+            let ty = &field.ty;
+            let read = if attrs.flatten {
+                quote! { <#ty as ::duckdb::FromRow>::from_row(row)? }
+            } else {
+                let column = match (&field.ident, attrs.rename) {
+                    (_, Some(rename)) => quote! { #rename },
+                    (Some(ident), None) => {
+                        let name = ident.unraw().to_string();
+                        quote! { #name }
+                    }
+                    (None, None) => quote! { #idx },
+                };
+                if attrs.default {
+                    quote! {
+                        match row.get::<_, #ty>(#column) {
+                            ::std::result::Result::Err(
+                                ::duckdb::Error::InvalidColumnName(_) | ::duckdb::Error::InvalidColumnIndex(_),
+                            ) => ::std::default::Default::default(),
+                            value => value?,
+                        }
+                    }
+                } else {
+                    quote! { row.get::<_, #ty>(#column)? }
+                }
+            };
+            Ok(match &field.ident {
+                Some(ident) => quote! { #ident: #read },
+                None => read,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let value = match fields {
+        syn::Fields::Named(_) => quote! { Self { #(#reads),* } },
+        syn::Fields::Unnamed(_) => quote! { Self(#(#reads),*) },
+        syn::Fields::Unit => quote! { Self },
+    };
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::duckdb::FromRow for #name #ty_generics #where_clause {
+            fn from_row(row: &::duckdb::Row<'_>) -> ::duckdb::Result<Self> {
+                ::std::result::Result::Ok(#value)
+            }
+        }
+    })
+}
+
+/// Derives `duckdb::ToRow` for a struct.
+///
+/// The fields are the values of the row in declaration order, a field with `#[duckdb(flatten)]`, itself a `ToRow`,
+/// contributes all of its values.
+#[proc_macro_derive(ToRow, attributes(duckdb))]
+pub fn derive_to_row(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    match to_row(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn to_row(ast: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = struct_fields(ast, "ToRow")?;
+    let pushes = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let attrs = FieldAttrs::parse(field)?;
+            let member = match &field.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let idx = syn::Index::from(idx);
+                    quote! { #idx }
+                }
+            };
+            Ok(if attrs.flatten {
+                quote! { values.extend(::duckdb::ToRow::to_row(&self.#member)); }
+            } else {
+                quote! { values.push(&self.#member); }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::duckdb::ToRow for #name #ty_generics #where_clause {
+            fn to_row(&self) -> ::std::vec::Vec<&dyn ::duckdb::ToSql> {
+                #[allow(unused_mut)]
+                let mut values: ::std::vec::Vec<&dyn ::duckdb::ToSql> = ::std::vec::Vec::new();
+                #(#pushes)*
+                values
+            }
+        }
+    })
+}
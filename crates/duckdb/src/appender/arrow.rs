@@ -2,7 +2,7 @@ use super::{ffi, Appender, Result};
 use crate::{
     core::{DataChunkHandle, LogicalTypeHandle},
     error::result_from_duckdb_appender,
-    vtab::{record_batch_to_duckdb_data_chunk, to_duckdb_logical_type},
+    vtab::{array_to_duckdb_logical_type, record_batch_to_duckdb_data_chunk},
     Error,
 };
 use arrow::record_batch::RecordBatch;
@@ -30,8 +30,8 @@ impl Appender<'_> {
     pub fn append_record_batch(&mut self, record_batch: RecordBatch) -> Result<()> {
         let schema = record_batch.schema();
         let mut logical_type: Vec<LogicalTypeHandle> = vec![];
-        for field in schema.fields() {
-            let logical_t = to_duckdb_logical_type(field.data_type())
+        for (field, column) in schema.fields().iter().zip(record_batch.columns()) {
+            let logical_t = array_to_duckdb_logical_type(column.as_ref())
                 .map_err(|_op| Error::ArrowTypeToDuckdbType(field.to_string(), field.data_type().clone()))?;
             logical_type.push(logical_t);
         }
@@ -48,8 +48,8 @@ impl Appender<'_> {
 mod test {
     use crate::{Connection, Result};
     use arrow::{
-        array::{Int8Array, StringArray},
-        datatypes::{DataType, Field, Schema},
+        array::{ArrayRef, DictionaryArray, Int8Array, StringArray},
+        datatypes::{DataType, Field, Int32Type, Schema},
         record_batch::RecordBatch,
     };
     use std::sync::Arc;
@@ -80,4 +80,35 @@ mod test {
         assert_eq!(rbs.iter().map(|op| op.num_rows()).sum::<usize>(), 5);
         Ok(())
     }
+
+    #[test]
+    fn test_append_dictionary() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TYPE color AS ENUM ('red', 'green', 'blue'); CREATE TABLE foo(c color, s VARCHAR)")?;
+        {
+            // the dictionaries don't have to match the order of the enum members
+            let colors: DictionaryArray<Int32Type> = vec![Some("blue"), None, Some("red")].into_iter().collect();
+            let strings: DictionaryArray<Int32Type> = vec!["x", "y", "x"].into_iter().collect();
+            let record_batch = RecordBatch::try_from_iter(vec![
+                ("c", Arc::new(colors) as ArrayRef),
+                ("s", Arc::new(strings) as ArrayRef),
+            ])
+            .unwrap();
+            let mut app = db.appender("foo")?;
+            app.append_record_batch(record_batch)?;
+        }
+        let mut stmt = db.prepare("SELECT c::VARCHAR, s FROM foo")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(Option<String>, String)>>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                (Some("blue".to_string()), "x".to_string()),
+                (None, "y".to_string()),
+                (Some("red".to_string()), "x".to_string()),
+            ]
+        );
+        Ok(())
+    }
 }
@@ -2,8 +2,9 @@ use super::{ffi, AppenderParams, Connection, Result, ValueRef};
 use std::{ffi::c_void, fmt, os::raw::c_char};
 
 use crate::{
+    core,
     error::result_from_duckdb_appender,
-    types::{ToSql, ToSqlOutput},
+    types::{ToSql, ToSqlOutput, Value},
     Error,
 };
 
@@ -70,6 +71,39 @@ impl Appender<'_> {
         result_from_duckdb_appender(rc, &mut self.app)
     }
 
+    /// Append one row from a value that implements `serde::Serialize`
+    ///
+    /// The fields of a struct or the elements of a tuple are appended in order, see [`crate::serde`] for how they
+    /// map to DuckDB values.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// # use serde::Serialize;
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// fn insert_person(conn: &Connection, person: &Person) -> Result<()> {
+    ///     let mut app = conn.appender("people")?;
+    ///     app.append_serialize(person)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the value is not a struct or a tuple, or if append column count not the same with the
+    /// table schema
+    #[cfg(feature = "serde")]
+    pub fn append_serialize<T: ?Sized + serde::Serialize>(&mut self, row: &T) -> Result<()> {
+        let values = crate::serde::to_row(row)?;
+        self.append_row(crate::appender_params_from_iter(values))
+    }
+
     #[inline]
     pub(crate) fn bind_parameters<P>(&mut self, params: P) -> Result<()>
     where
@@ -88,6 +122,7 @@ impl Appender<'_> {
         let ptr = self.app;
         let value = match value {
             ToSqlOutput::Borrowed(v) => v,
+            ToSqlOutput::Owned(ref v) if v.is_nested() => return self.append_nested(v),
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
         };
         // NOTE: we ignore the return value here
@@ -114,6 +149,10 @@ impl Appender<'_> {
 
             ValueRef::Float(r) => unsafe { ffi::duckdb_append_float(ptr, r) },
             ValueRef::Double(r) => unsafe { ffi::duckdb_append_double(ptr, r) },
+            ValueRef::Decimal(d) => unsafe {
+                let value = core::Value::from(ffi::duckdb_decimal::from(d.checked()?));
+                ffi::duckdb_append_value(ptr, value.ptr)
+            },
             ValueRef::Text(s) => unsafe {
                 ffi::duckdb_append_varchar_length(ptr, s.as_ptr() as *const c_char, s.len() as u64)
             },
@@ -143,6 +182,16 @@ impl Appender<'_> {
         Ok(())
     }
 
+    /// Append a nested value as a `duckdb_value`, `MAP` and `UNION` values cannot be created and are an error.
+    fn append_nested(&self, value: &Value) -> Result<()> {
+        let value = core::Value::try_from(value)?;
+        let rc = unsafe { ffi::duckdb_append_value(self.app, value.ptr) };
+        if rc != 0 {
+            return Err(Error::AppendError);
+        }
+        Ok(())
+    }
+
     #[inline]
     pub(super) fn new(conn: &Connection, app: ffi::duckdb_appender) -> Appender<'_> {
         Appender { conn, app }
@@ -1,4 +1,4 @@
-use crate::{Appender, Result, ToSql};
+use crate::{Appender, Result, ToRow, ToSql};
 
 mod sealed {
     /// This trait exists just to ensure that the only impls of `trait Params`
@@ -177,6 +177,14 @@ impl_for_array_ref!(
     18 19 20 21 22 23 24 25 26 27 29 30 31 32
 );
 
+impl<T: ToRow + ?Sized> Sealed for &T {}
+impl<T: ToRow + ?Sized> AppenderParams for &T {
+    #[inline]
+    fn __bind_in(self, stmt: &mut Appender<'_>) -> Result<()> {
+        stmt.bind_parameters(self.to_row())
+    }
+}
+
 /// Adapter type which allows any iterator over [`ToSql`] values to implement
 /// [`Params`].
 ///
@@ -1,7 +1,16 @@
 use super::{
-    arrow::{datatypes::SchemaRef, record_batch::RecordBatch},
+    arrow::{
+        datatypes::SchemaRef,
+        error::ArrowError,
+        ffi_stream::FFI_ArrowArrayStream,
+        record_batch::{RecordBatch, RecordBatchReader},
+    },
+    ffi,
+    inner_connection::RawDatabase,
+    raw_statement::arrow_result_step,
     Statement,
 };
+use std::sync::Arc;
 
 /// A handle for the resulting RecordBatch of a query.
 #[must_use = "Arrow is lazy and will do nothing unless consumed"]
@@ -65,3 +74,63 @@ impl<'stmt> Iterator for ArrowStream<'stmt> {
         Some(RecordBatch::from(&self.stmt?.stream_step(self.get_schema())?))
     }
 }
+
+/// An owned handle for the resulting RecordBatches of a query.
+///
+/// Unlike [`Arrow`] the reader does not borrow the statement, so it can be handed to any Arrow consumer, also across
+/// language boundaries by converting it into an [`FFI_ArrowArrayStream`].
+///
+/// The reader keeps the database open until it is dropped, also when the connection is dropped before. A database
+/// passed to [`Connection::open_from_raw`](crate::Connection::open_from_raw) is owned by the caller and must outlive
+/// the reader.
+#[must_use = "ArrowReader is lazy and will do nothing unless consumed"]
+pub struct ArrowReader {
+    result: ffi::duckdb_arrow,
+    schema: SchemaRef,
+    // the materialized result lives in the buffers of the database, so it must stay open
+    _database: Option<Arc<RawDatabase>>,
+}
+
+// The result is materialized and does not refer to the statement or the connection, only to the database which is
+// kept open.
+unsafe impl Send for ArrowReader {}
+
+impl ArrowReader {
+    #[inline]
+    pub(crate) fn new(result: ffi::duckdb_arrow, schema: SchemaRef, database: Option<Arc<RawDatabase>>) -> ArrowReader {
+        ArrowReader {
+            result,
+            schema,
+            _database: database,
+        }
+    }
+}
+
+impl Iterator for ArrowReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let struct_array = unsafe { arrow_result_step(self.result) }?;
+        Some(Ok(RecordBatch::from(&struct_array)))
+    }
+}
+
+impl RecordBatchReader for ArrowReader {
+    #[inline]
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl From<ArrowReader> for FFI_ArrowArrayStream {
+    #[inline]
+    fn from(reader: ArrowReader) -> FFI_ArrowArrayStream {
+        FFI_ArrowArrayStream::new(Box::new(reader))
+    }
+}
+
+impl Drop for ArrowReader {
+    fn drop(&mut self) {
+        unsafe { ffi::duckdb_destroy_arrow(&mut self.result) };
+    }
+}
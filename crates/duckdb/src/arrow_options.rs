@@ -0,0 +1,172 @@
+use crate::{pragma::Sql, Connection, Result};
+
+/// The Arrow format of query results.
+///
+/// By default DuckDB produces `Utf8` strings, `Binary` blobs and `List`s with 32-bit offsets, and exports types without
+/// a native Arrow equivalent, such as `UUID` and `HUGEINT`, as the closest lossy Arrow type. Every option requests a
+/// different format, see [`Connection::set_arrow_options`].
+///
+/// Apart from the time zone, the format is a setting of the database, not of a connection or statement.
+///
+/// ```rust,no_run
+/// # use duckdb::{ArrowOptions, Connection, Result};
+/// fn use_large_strings(conn: &Connection) -> Result<()> {
+///     conn.set_arrow_options(&ArrowOptions::new().large_buffers(true))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArrowOptions {
+    large_buffers: bool,
+    string_view: bool,
+    lossless_conversion: bool,
+    time_zone: Option<String>,
+}
+
+impl ArrowOptions {
+    /// The default Arrow format.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use 64-bit offsets, i.e. `LargeUtf8`, `LargeBinary` and `LargeList`.
+    pub fn large_buffers(mut self, enabled: bool) -> Self {
+        self.large_buffers = enabled;
+        self
+    }
+
+    /// Produce strings as `Utf8View`, this takes precedence over [`ArrowOptions::large_buffers`] for strings.
+    pub fn string_view(mut self, enabled: bool) -> Self {
+        self.string_view = enabled;
+        self
+    }
+
+    /// Produce types without a native Arrow equivalent as extension types instead of converting them, e.g. `UUID` as
+    /// `arrow.uuid` and `HUGEINT` as `duckdb.hugeint`.
+    pub fn lossless_conversion(mut self, enabled: bool) -> Self {
+        self.lossless_conversion = enabled;
+        self
+    }
+
+    /// The time zone of `TIMESTAMP WITH TIME ZONE` columns, `UTC` unless set.
+    ///
+    /// Setting the time zone requires the `icu` extension.
+    pub fn time_zone(mut self, time_zone: impl Into<String>) -> Self {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+/// The settings behind the options, in the order of the fields of [`ArrowOptions`].
+const SETTINGS: [&str; 3] = [
+    "arrow_large_buffer_size",
+    "produce_arrow_string_view",
+    "arrow_lossless_conversion",
+];
+
+impl Connection {
+    /// Set the Arrow format of query results, used by [`Statement::query_arrow`](crate::Statement::query_arrow),
+    /// [`Statement::stream_arrow`](crate::Statement::stream_arrow) and friends.
+    ///
+    /// The format is a setting of the database, so it also applies to all other connections to the same database,
+    /// including queries they are executing at the same time. Only the time zone applies to this connection alone. A
+    /// time zone of `None` leaves the time zone of the connection unchanged.
+    pub fn set_arrow_options(&self, options: &ArrowOptions) -> Result<()> {
+        let enabled = [options.large_buffers, options.string_view, options.lossless_conversion];
+        let mut sql = String::new();
+        for (setting, enabled) in SETTINGS.iter().zip(enabled) {
+            sql.push_str(&format!("SET {setting} = {enabled};"));
+        }
+        if let Some(time_zone) = &options.time_zone {
+            let mut value = Sql::new();
+            value.push_string_literal(time_zone);
+            sql.push_str(&format!("SET TimeZone = {};", value.as_str()));
+        }
+        self.execute_batch(&sql)
+    }
+
+    /// Get the current Arrow format of query results, see [`Connection::set_arrow_options`].
+    ///
+    /// The time zone is `None` when the `icu` extension is not loaded.
+    pub fn arrow_options(&self) -> Result<ArrowOptions> {
+        let mut stmt = self.prepare("SELECT name, value FROM duckdb_settings() WHERE name IN (?, ?, ?, 'TimeZone')")?;
+        let mut rows = stmt.query(SETTINGS)?;
+        let mut options = ArrowOptions::new();
+        while let Some(row) = rows.next()? {
+            let name: String = row.get(0)?;
+            let value: String = row.get(1)?;
+            let enabled = value == "true";
+            match name.as_str() {
+                "arrow_large_buffer_size" => options.large_buffers = enabled,
+                "produce_arrow_string_view" => options.string_view = enabled,
+                "arrow_lossless_conversion" => options.lossless_conversion = enabled,
+                _ => options.time_zone = Some(value),
+            }
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow::datatypes::DataType;
+
+    use crate::{ArrowOptions, Connection, Result};
+
+    #[test]
+    fn test_arrow_options() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        assert_eq!(conn.arrow_options()?, ArrowOptions::new());
+
+        let options = ArrowOptions::new().large_buffers(true).lossless_conversion(true);
+        conn.set_arrow_options(&options)?;
+        assert_eq!(conn.arrow_options()?, options);
+
+        // the format is shared by all connections to the database
+        assert_eq!(conn.try_clone()?.arrow_options()?, options);
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_arrow_formats() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let sql = "SELECT 'a' AS s, '\\x2A'::BLOB AS b, [1, 2] AS l, 1::HUGEINT AS h, gen_random_uuid() AS u";
+        let mut stmt = conn.prepare(sql)?;
+
+        let mut types = |options: &ArrowOptions| -> Result<Vec<DataType>> {
+            conn.set_arrow_options(options)?;
+            let schema = stmt.query_arrow([])?.get_schema();
+            Ok(schema.fields().iter().map(|field| field.data_type().clone()).collect())
+        };
+
+        let default = types(&ArrowOptions::new())?;
+        assert_eq!(default[0], DataType::Utf8);
+        assert_eq!(default[1], DataType::Binary);
+        assert!(matches!(default[2], DataType::List(_)));
+        assert_eq!(default[3], DataType::Decimal128(38, 0));
+        assert_eq!(default[4], DataType::Utf8);
+
+        let large = types(&ArrowOptions::new().large_buffers(true))?;
+        assert_eq!(large[0], DataType::LargeUtf8);
+        assert_eq!(large[1], DataType::LargeBinary);
+        assert!(matches!(large[2], DataType::LargeList(_)));
+
+        let views = types(&ArrowOptions::new().string_view(true))?;
+        assert_eq!(views[0], DataType::Utf8View);
+
+        types(&ArrowOptions::new().lossless_conversion(true))?;
+        let schema = conn.prepare(sql)?.query_arrow([])?.get_schema();
+        for name in ["h", "u"] {
+            let field = schema.field_with_name(name).unwrap();
+            assert_eq!(field.data_type(), &DataType::FixedSizeBinary(16));
+            assert!(field.metadata().contains_key("ARROW:extension:name"));
+        }
+
+        // a stream has the format set when it starts
+        conn.set_arrow_options(&ArrowOptions::new().large_buffers(true))?;
+        let schema = conn.prepare(sql)?.query_arrow([])?.get_schema();
+        let mut stmt = conn.prepare(sql)?;
+        let batches: Vec<_> = stmt.stream_arrow([], schema)?.collect();
+        assert_eq!(batches[0].schema().field(0).data_type(), &DataType::LargeUtf8);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
     logical_type::LogicalTypeHandle,
-    vector::{ArrayVector, FlatVector, ListVector, StructVector},
+    vector::{ArrayVector, FlatVector, ListVector, MapVector, StructVector, UnionVector},
 };
 use crate::ffi::{
     duckdb_create_data_chunk, duckdb_data_chunk, duckdb_data_chunk_get_column_count, duckdb_data_chunk_get_size,
@@ -73,6 +73,16 @@ impl DataChunkHandle {
         StructVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
     }
 
+    /// Get map vector at the column index: `idx`.
+    pub fn map_vector(&self, idx: usize) -> MapVector {
+        MapVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
+    }
+
+    /// Get union vector at the column index: `idx`.
+    pub fn union_vector(&self, idx: usize) -> UnionVector {
+        UnionVector::from(unsafe { duckdb_data_chunk_get_vector(self.ptr, idx as u64) })
+    }
+
     /// Set the size of the data chunk
     pub fn set_len(&self, new_len: usize) {
         unsafe { duckdb_data_chunk_set_size(self.ptr, new_len as u64) };
@@ -1,14 +1,17 @@
 use std::{
-    ffi::{c_char, CString},
+    ffi::{c_char, CStr, CString},
     fmt::Debug,
+    sync::Arc,
 };
 
-use crate::ffi::*;
+use arrow::datatypes::{DataType, Field, Fields, IntervalUnit, TimeUnit, UnionFields, UnionMode};
+
+use crate::{ffi, ffi::*, inner_connection::InnerConnection, types::Type, Connection, Error, Result};
 
 /// Logical Type Id
 /// <https://duckdb.org/docs/api/c/types>
 #[repr(u32)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogicalTypeId {
     Invalid = DUCKDB_TYPE_DUCKDB_TYPE_INVALID,
     /// Boolean
@@ -43,6 +46,8 @@ pub enum LogicalTypeId {
     Interval = DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL,
     /// Hugeint
     Hugeint = DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT,
+    /// Unsigned Hugeint
+    UHugeint = DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT,
     /// Varchar
     Varchar = DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR,
     /// Blob
@@ -67,14 +72,22 @@ pub enum LogicalTypeId {
     Uuid = DUCKDB_TYPE_DUCKDB_TYPE_UUID,
     /// Union
     Union = DUCKDB_TYPE_DUCKDB_TYPE_UNION,
+    /// Bit
+    Bit = DUCKDB_TYPE_DUCKDB_TYPE_BIT,
+    /// Time TZ
+    TimeTZ = DUCKDB_TYPE_DUCKDB_TYPE_TIME_TZ,
     /// Timestamp TZ
     TimestampTZ = DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ,
+    /// Array
+    Array = DUCKDB_TYPE_DUCKDB_TYPE_ARRAY,
+    /// Varint
+    Varint = DUCKDB_TYPE_DUCKDB_TYPE_VARINT,
 
     SQLNull = DUCKDB_TYPE_DUCKDB_TYPE_SQLNULL,
 }
 
 impl From<u32> for LogicalTypeId {
-    /// Convert from u32 to LogicalTypeId
+    /// Convert from u32 to LogicalTypeId, ids unknown to this version are [`LogicalTypeId::Invalid`]
     fn from(value: u32) -> Self {
         match value {
             DUCKDB_TYPE_DUCKDB_TYPE_BOOLEAN => Self::Boolean,
@@ -95,6 +108,7 @@ impl From<u32> for LogicalTypeId {
             DUCKDB_TYPE_DUCKDB_TYPE_TIME => Self::Time,
             DUCKDB_TYPE_DUCKDB_TYPE_INTERVAL => Self::Interval,
             DUCKDB_TYPE_DUCKDB_TYPE_HUGEINT => Self::Hugeint,
+            DUCKDB_TYPE_DUCKDB_TYPE_UHUGEINT => Self::UHugeint,
             DUCKDB_TYPE_DUCKDB_TYPE_DECIMAL => Self::Decimal,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_S => Self::TimestampS,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_MS => Self::TimestampMs,
@@ -105,8 +119,13 @@ impl From<u32> for LogicalTypeId {
             DUCKDB_TYPE_DUCKDB_TYPE_MAP => Self::Map,
             DUCKDB_TYPE_DUCKDB_TYPE_UUID => Self::Uuid,
             DUCKDB_TYPE_DUCKDB_TYPE_UNION => Self::Union,
+            DUCKDB_TYPE_DUCKDB_TYPE_BIT => Self::Bit,
+            DUCKDB_TYPE_DUCKDB_TYPE_TIME_TZ => Self::TimeTZ,
             DUCKDB_TYPE_DUCKDB_TYPE_TIMESTAMP_TZ => Self::TimestampTZ,
-            _ => panic!(),
+            DUCKDB_TYPE_DUCKDB_TYPE_ARRAY => Self::Array,
+            DUCKDB_TYPE_DUCKDB_TYPE_VARINT => Self::Varint,
+            DUCKDB_TYPE_DUCKDB_TYPE_SQLNULL => Self::SQLNull,
+            _ => Self::Invalid,
         }
     }
 }
@@ -230,38 +249,186 @@ impl LogicalTypeHandle {
     }
 
     /// Make a `LogicalType` for `struct`
+    ///
+    /// Panics if a field name contains a NUL byte, see [`LogicalTypeHandle::try_struct_type`].
     pub fn struct_type(fields: &[(&str, LogicalTypeHandle)]) -> Self {
-        let keys: Vec<CString> = fields.iter().map(|f| CString::new(f.0).unwrap()).collect();
+        Self::try_struct_type(fields).expect("field names should not contain a NUL byte")
+    }
+
+    /// Make a `LogicalType` for `struct`
+    ///
+    /// Returns an `Error::NulError` if a field name contains a NUL byte.
+    pub fn try_struct_type(fields: &[(&str, LogicalTypeHandle)]) -> crate::Result<Self> {
+        let keys = fields
+            .iter()
+            .map(|f| CString::new(f.0))
+            .collect::<Result<Vec<_>, _>>()?;
         let values: Vec<duckdb_logical_type> = fields.iter().map(|it| it.1.ptr).collect();
         let name_ptrs = keys.iter().map(|it| it.as_ptr()).collect::<Vec<*const c_char>>();
 
         unsafe {
-            Self {
+            Ok(Self {
                 ptr: duckdb_create_struct_type(
                     values.as_slice().as_ptr().cast_mut(),
                     name_ptrs.as_slice().as_ptr().cast_mut(),
                     fields.len() as idx_t,
                 ),
                 owned: true,
-            }
+            })
         }
     }
 
     /// Make a `LogicalType` for `union`
+    ///
+    /// Panics if a member name contains a NUL byte, see [`LogicalTypeHandle::try_union_type`].
     pub fn union_type(fields: &[(&str, LogicalTypeHandle)]) -> Self {
-        let keys: Vec<CString> = fields.iter().map(|f| CString::new(f.0).unwrap()).collect();
+        Self::try_union_type(fields).expect("member names should not contain a NUL byte")
+    }
+
+    /// Make a `LogicalType` for `union`
+    ///
+    /// Returns an `Error::NulError` if a member name contains a NUL byte.
+    pub fn try_union_type(fields: &[(&str, LogicalTypeHandle)]) -> crate::Result<Self> {
+        let keys = fields
+            .iter()
+            .map(|f| CString::new(f.0))
+            .collect::<Result<Vec<_>, _>>()?;
         let values: Vec<duckdb_logical_type> = fields.iter().map(|it| it.1.ptr).collect();
         let name_ptrs = keys.iter().map(|it| it.as_ptr()).collect::<Vec<*const c_char>>();
 
         unsafe {
-            Self {
+            Ok(Self {
                 ptr: duckdb_create_union_type(
                     values.as_slice().as_ptr().cast_mut(),
                     name_ptrs.as_slice().as_ptr().cast_mut(),
                     fields.len() as idx_t,
                 ),
                 owned: true,
+            })
+        }
+    }
+
+    /// Make a `LogicalType` for `enum` with the given members
+    ///
+    /// Returns an `Error::NulError` if a member contains a NUL byte.
+    pub fn enum_type(members: &[&str]) -> crate::Result<Self> {
+        let members = members
+            .iter()
+            .map(|m| CString::new(*m))
+            .collect::<Result<Vec<_>, _>>()?;
+        let member_ptrs = members.iter().map(|it| it.as_ptr()).collect::<Vec<*const c_char>>();
+
+        unsafe {
+            Ok(Self {
+                ptr: duckdb_create_enum_type(member_ptrs.as_slice().as_ptr().cast_mut(), members.len() as idx_t),
+                owned: true,
+            })
+        }
+    }
+
+    /// Retrieves the number of members of an enum
+    /// Returns 0 if the LogicalType is not an enum
+    pub fn enum_dictionary_size(&self) -> usize {
+        unsafe { duckdb_enum_dictionary_size(self.ptr) as usize }
+    }
+
+    /// Retrieves the member of an enum at `idx`
+    ///
+    /// Panics if the logical type is not an enum
+    pub fn enum_dictionary_value(&self, idx: usize) -> String {
+        assert_eq!(self.id(), LogicalTypeId::Enum, "not an enum");
+        unsafe {
+            let value_ptr = duckdb_enum_dictionary_value(self.ptr, idx as idx_t);
+            let value = CStr::from_ptr(value_ptr).to_string_lossy().into_owned();
+            duckdb_free(value_ptr.cast());
+            value
+        }
+    }
+
+    /// Retrieves all members of an enum, in order
+    ///
+    /// Panics if the logical type is not an enum
+    pub fn enum_values(&self) -> Vec<String> {
+        (0..self.enum_dictionary_size())
+            .map(|idx| self.enum_dictionary_value(idx))
+            .collect()
+    }
+
+    /// Retrieves the physical type of the indices of an enum, one of `UTinyint`, `USmallint` or `UInteger`
+    ///
+    /// Panics if the logical type is not an enum
+    pub fn enum_internal_type(&self) -> LogicalTypeId {
+        assert_eq!(self.id(), LogicalTypeId::Enum, "not an enum");
+        unsafe { duckdb_enum_internal_type(self.ptr) }.into()
+    }
+
+    /// Retrieves the physical type a decimal is stored as, one of `Smallint`, `Integer`, `Bigint` or `Hugeint`
+    ///
+    /// Panics if the logical type is not a decimal
+    pub fn decimal_internal_type(&self) -> LogicalTypeId {
+        assert_eq!(self.id(), LogicalTypeId::Decimal, "not a decimal");
+        unsafe { duckdb_decimal_internal_type(self.ptr) }.into()
+    }
+
+    /// Retrieves the element type of a list
+    ///
+    /// Panics if the logical type is not a list
+    pub fn list_child_type(&self) -> Self {
+        assert_eq!(self.id(), LogicalTypeId::List, "not a list");
+        unsafe { Self::new(duckdb_list_type_child_type(self.ptr)) }
+    }
+
+    /// Retrieves the element type of an array
+    ///
+    /// Panics if the logical type is not an array
+    pub fn array_child_type(&self) -> Self {
+        assert_eq!(self.id(), LogicalTypeId::Array, "not an array");
+        unsafe { Self::new(duckdb_array_type_child_type(self.ptr)) }
+    }
+
+    /// Retrieves the number of elements of an array
+    ///
+    /// Panics if the logical type is not an array
+    pub fn array_size(&self) -> u64 {
+        assert_eq!(self.id(), LogicalTypeId::Array, "not an array");
+        unsafe { duckdb_array_type_array_size(self.ptr) }
+    }
+
+    /// Retrieves the key type of a map
+    ///
+    /// Panics if the logical type is not a map
+    pub fn map_key_type(&self) -> Self {
+        assert_eq!(self.id(), LogicalTypeId::Map, "not a map");
+        unsafe { Self::new(duckdb_map_type_key_type(self.ptr)) }
+    }
+
+    /// Retrieves the value type of a map
+    ///
+    /// Panics if the logical type is not a map
+    pub fn map_value_type(&self) -> Self {
+        assert_eq!(self.id(), LogicalTypeId::Map, "not a map");
+        unsafe { Self::new(duckdb_map_type_value_type(self.ptr)) }
+    }
+
+    /// Sets the alias of the logical type, e.g. to register it with [`Connection::register_logical_type`]
+    ///
+    /// Returns an `Error::NulError` if the alias contains a NUL byte.
+    pub fn set_alias(&mut self, alias: &str) -> crate::Result<()> {
+        let alias = CString::new(alias)?;
+        unsafe { duckdb_logical_type_set_alias(self.ptr, alias.as_ptr()) };
+        Ok(())
+    }
+
+    /// Retrieves the alias of the logical type, if it has one
+    pub fn alias(&self) -> Option<String> {
+        unsafe {
+            let alias_ptr = duckdb_logical_type_get_alias(self.ptr);
+            if alias_ptr.is_null() {
+                return None;
             }
+            let alias = CStr::from_ptr(alias_ptr).to_string_lossy().into_owned();
+            duckdb_free(alias_ptr.cast());
+            Some(alias)
         }
     }
 
@@ -317,9 +484,379 @@ impl LogicalTypeHandle {
     }
 }
 
+/// A DuckDB logical type with all of its children, the owned and comparable counterpart of [`LogicalTypeHandle`]
+///
+/// ```rust
+/// # use duckdb::core::{LogicalType, LogicalTypeHandle};
+/// let point = LogicalType::Struct(vec![("x".to_owned(), LogicalType::Double), ("y".to_owned(), LogicalType::Double)]);
+/// let path = LogicalType::List(Box::new(point));
+/// let handle = LogicalTypeHandle::try_from(&path)?;
+/// assert_eq!(LogicalType::try_from(&handle)?, path);
+/// # Ok::<(), duckdb::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicalType {
+    /// NULL, the type of an untyped `NULL` literal
+    SQLNull,
+    /// BOOLEAN
+    Boolean,
+    /// TINYINT
+    Tinyint,
+    /// SMALLINT
+    Smallint,
+    /// INTEGER
+    Integer,
+    /// BIGINT
+    Bigint,
+    /// UTINYINT
+    UTinyint,
+    /// USMALLINT
+    USmallint,
+    /// UINTEGER
+    UInteger,
+    /// UBIGINT
+    UBigint,
+    /// HUGEINT
+    Hugeint,
+    /// FLOAT
+    Float,
+    /// DOUBLE
+    Double,
+    /// DECIMAL with its total number of digits and the number of digits after the decimal point
+    Decimal {
+        /// The total number of digits
+        width: u8,
+        /// The number of digits after the decimal point
+        scale: u8,
+    },
+    /// TIMESTAMP, in microseconds
+    Timestamp,
+    /// TIMESTAMP_S, in seconds
+    TimestampS,
+    /// TIMESTAMP_MS, in milliseconds
+    TimestampMs,
+    /// TIMESTAMP_NS, in nanoseconds
+    TimestampNs,
+    /// TIMESTAMP WITH TIME ZONE, in microseconds
+    TimestampTZ,
+    /// DATE
+    Date,
+    /// TIME
+    Time,
+    /// INTERVAL
+    Interval,
+    /// VARCHAR
+    Varchar,
+    /// BLOB
+    Blob,
+    /// UUID
+    Uuid,
+    /// ENUM with its members
+    Enum(Vec<String>),
+    /// LIST of its element type
+    List(Box<LogicalType>),
+    /// ARRAY of its element type and number of elements
+    Array(Box<LogicalType>, u64),
+    /// MAP from its key type to its value type
+    Map(Box<LogicalType>, Box<LogicalType>),
+    /// STRUCT with its named fields
+    Struct(Vec<(String, LogicalType)>),
+    /// UNION with its named members
+    Union(Vec<(String, LogicalType)>),
+}
+
+impl LogicalType {
+    /// The id of the logical type
+    pub fn id(&self) -> LogicalTypeId {
+        match self {
+            Self::SQLNull => LogicalTypeId::SQLNull,
+            Self::Boolean => LogicalTypeId::Boolean,
+            Self::Tinyint => LogicalTypeId::Tinyint,
+            Self::Smallint => LogicalTypeId::Smallint,
+            Self::Integer => LogicalTypeId::Integer,
+            Self::Bigint => LogicalTypeId::Bigint,
+            Self::UTinyint => LogicalTypeId::UTinyint,
+            Self::USmallint => LogicalTypeId::USmallint,
+            Self::UInteger => LogicalTypeId::UInteger,
+            Self::UBigint => LogicalTypeId::UBigint,
+            Self::Hugeint => LogicalTypeId::Hugeint,
+            Self::Float => LogicalTypeId::Float,
+            Self::Double => LogicalTypeId::Double,
+            Self::Decimal { .. } => LogicalTypeId::Decimal,
+            Self::Timestamp => LogicalTypeId::Timestamp,
+            Self::TimestampS => LogicalTypeId::TimestampS,
+            Self::TimestampMs => LogicalTypeId::TimestampMs,
+            Self::TimestampNs => LogicalTypeId::TimestampNs,
+            Self::TimestampTZ => LogicalTypeId::TimestampTZ,
+            Self::Date => LogicalTypeId::Date,
+            Self::Time => LogicalTypeId::Time,
+            Self::Interval => LogicalTypeId::Interval,
+            Self::Varchar => LogicalTypeId::Varchar,
+            Self::Blob => LogicalTypeId::Blob,
+            Self::Uuid => LogicalTypeId::Uuid,
+            Self::Enum(_) => LogicalTypeId::Enum,
+            Self::List(_) => LogicalTypeId::List,
+            Self::Array(..) => LogicalTypeId::Array,
+            Self::Map(..) => LogicalTypeId::Map,
+            Self::Struct(_) => LogicalTypeId::Struct,
+            Self::Union(_) => LogicalTypeId::Union,
+        }
+    }
+}
+
+impl TryFrom<&LogicalTypeHandle> for LogicalType {
+    type Error = Error;
+
+    /// Read the logical type and all of its children from the handle
+    ///
+    /// Fails for types [`LogicalType`] has no variant for, e.g. `UHUGEINT`, `TIME WITH TIME ZONE`, `BIT` and `VARINT`,
+    /// also when they are nested in the type.
+    fn try_from(handle: &LogicalTypeHandle) -> Result<Self> {
+        let children = || {
+            (0..handle.num_children())
+                .map(|idx| Ok((handle.child_name(idx), Self::try_from(&handle.child(idx))?)))
+                .collect::<Result<_>>()
+        };
+        Ok(match handle.id() {
+            LogicalTypeId::SQLNull => Self::SQLNull,
+            LogicalTypeId::Boolean => Self::Boolean,
+            LogicalTypeId::Tinyint => Self::Tinyint,
+            LogicalTypeId::Smallint => Self::Smallint,
+            LogicalTypeId::Integer => Self::Integer,
+            LogicalTypeId::Bigint => Self::Bigint,
+            LogicalTypeId::UTinyint => Self::UTinyint,
+            LogicalTypeId::USmallint => Self::USmallint,
+            LogicalTypeId::UInteger => Self::UInteger,
+            LogicalTypeId::UBigint => Self::UBigint,
+            LogicalTypeId::Hugeint => Self::Hugeint,
+            LogicalTypeId::Float => Self::Float,
+            LogicalTypeId::Double => Self::Double,
+            LogicalTypeId::Decimal => Self::Decimal {
+                width: handle.decimal_width(),
+                scale: handle.decimal_scale(),
+            },
+            LogicalTypeId::Timestamp => Self::Timestamp,
+            LogicalTypeId::TimestampS => Self::TimestampS,
+            LogicalTypeId::TimestampMs => Self::TimestampMs,
+            LogicalTypeId::TimestampNs => Self::TimestampNs,
+            LogicalTypeId::TimestampTZ => Self::TimestampTZ,
+            LogicalTypeId::Date => Self::Date,
+            LogicalTypeId::Time => Self::Time,
+            LogicalTypeId::Interval => Self::Interval,
+            LogicalTypeId::Varchar => Self::Varchar,
+            LogicalTypeId::Blob => Self::Blob,
+            LogicalTypeId::Uuid => Self::Uuid,
+            LogicalTypeId::Enum => Self::Enum(handle.enum_values()),
+            LogicalTypeId::List => Self::List(Box::new(Self::try_from(&handle.list_child_type())?)),
+            LogicalTypeId::Array => Self::Array(
+                Box::new(Self::try_from(&handle.array_child_type())?),
+                handle.array_size(),
+            ),
+            LogicalTypeId::Map => Self::Map(
+                Box::new(Self::try_from(&handle.map_key_type())?),
+                Box::new(Self::try_from(&handle.map_value_type())?),
+            ),
+            LogicalTypeId::Struct => Self::Struct(children()?),
+            LogicalTypeId::Union => Self::Union(children()?),
+            id => {
+                return Err(Error::DuckDBFailure(
+                    ffi::Error::new(DuckDBError),
+                    Some(format!("the logical type {id:?} is not supported")),
+                ))
+            }
+        })
+    }
+}
+
+impl TryFrom<&LogicalType> for LogicalTypeHandle {
+    type Error = Error;
+
+    /// Create the handle of the logical type and all of its children
+    ///
+    /// Fails if the members of an `ENUM` or the names of `STRUCT` fields or `UNION` members contain NUL bytes.
+    fn try_from(logical_type: &LogicalType) -> Result<Self> {
+        fn children(fields: &[(String, LogicalType)]) -> Result<Vec<(&str, LogicalTypeHandle)>> {
+            fields
+                .iter()
+                .map(|(name, child)| Ok((name.as_str(), LogicalTypeHandle::try_from(child)?)))
+                .collect()
+        }
+        Ok(match logical_type {
+            LogicalType::Decimal { width, scale } => Self::decimal(*width, *scale),
+            LogicalType::Enum(members) => Self::enum_type(&members.iter().map(String::as_str).collect::<Vec<_>>())?,
+            LogicalType::List(child) => Self::list(&Self::try_from(child.as_ref())?),
+            LogicalType::Array(child, size) => Self::array(&Self::try_from(child.as_ref())?, *size),
+            LogicalType::Map(key, value) => Self::map(&Self::try_from(key.as_ref())?, &Self::try_from(value.as_ref())?),
+            LogicalType::Struct(fields) => Self::try_struct_type(&children(fields)?)?,
+            LogicalType::Union(members) => Self::try_union_type(&children(members)?)?,
+            other => Self::from(other.id()),
+        })
+    }
+}
+
+impl From<&LogicalType> for Type {
+    fn from(logical_type: &LogicalType) -> Self {
+        let children = |fields: &[(String, LogicalType)]| {
+            fields
+                .iter()
+                .map(|(name, child)| (name.clone(), Self::from(child)))
+                .collect()
+        };
+        match logical_type {
+            LogicalType::SQLNull => Self::Null,
+            LogicalType::Boolean => Self::Boolean,
+            LogicalType::Tinyint => Self::TinyInt,
+            LogicalType::Smallint => Self::SmallInt,
+            LogicalType::Integer => Self::Int,
+            LogicalType::Bigint => Self::BigInt,
+            LogicalType::UTinyint => Self::UTinyInt,
+            LogicalType::USmallint => Self::USmallInt,
+            LogicalType::UInteger => Self::UInt,
+            LogicalType::UBigint => Self::UBigInt,
+            LogicalType::Hugeint => Self::HugeInt,
+            LogicalType::Float => Self::Float,
+            LogicalType::Double => Self::Double,
+            LogicalType::Decimal { .. } => Self::Decimal,
+            LogicalType::Timestamp
+            | LogicalType::TimestampS
+            | LogicalType::TimestampMs
+            | LogicalType::TimestampNs
+            | LogicalType::TimestampTZ => Self::Timestamp,
+            LogicalType::Date => Self::Date32,
+            LogicalType::Time => Self::Time64,
+            LogicalType::Interval => Self::Interval,
+            // UUIDs are read as text
+            LogicalType::Varchar | LogicalType::Uuid => Self::Text,
+            LogicalType::Blob => Self::Blob,
+            LogicalType::Enum(_) => Self::Enum,
+            LogicalType::List(child) => Self::List(Box::new(Self::from(child.as_ref()))),
+            LogicalType::Array(child, size) => Self::Array(
+                Box::new(Self::from(child.as_ref())),
+                (*size).try_into().expect("array size should fit in a u32"),
+            ),
+            LogicalType::Map(key, value) => {
+                Self::Map(Box::new(Self::from(key.as_ref())), Box::new(Self::from(value.as_ref())))
+            }
+            LogicalType::Struct(fields) => Self::Struct(children(fields)),
+            LogicalType::Union(_) => Self::Union,
+        }
+    }
+}
+
+impl From<&LogicalType> for DataType {
+    /// The arrow type DuckDB exports the logical type as
+    fn from(logical_type: &LogicalType) -> Self {
+        let fields = |fields: &[(String, LogicalType)]| -> Fields {
+            fields
+                .iter()
+                .map(|(name, child)| Field::new(name, Self::from(child), true))
+                .collect()
+        };
+        match logical_type {
+            LogicalType::SQLNull => Self::Null,
+            LogicalType::Boolean => Self::Boolean,
+            LogicalType::Tinyint => Self::Int8,
+            LogicalType::Smallint => Self::Int16,
+            LogicalType::Integer => Self::Int32,
+            LogicalType::Bigint => Self::Int64,
+            LogicalType::UTinyint => Self::UInt8,
+            LogicalType::USmallint => Self::UInt16,
+            LogicalType::UInteger => Self::UInt32,
+            LogicalType::UBigint => Self::UInt64,
+            LogicalType::Hugeint => Self::Decimal128(38, 0),
+            LogicalType::Float => Self::Float32,
+            LogicalType::Double => Self::Float64,
+            LogicalType::Decimal { width, scale } => Self::Decimal128(*width, *scale as i8),
+            LogicalType::Timestamp => Self::Timestamp(TimeUnit::Microsecond, None),
+            LogicalType::TimestampS => Self::Timestamp(TimeUnit::Second, None),
+            LogicalType::TimestampMs => Self::Timestamp(TimeUnit::Millisecond, None),
+            LogicalType::TimestampNs => Self::Timestamp(TimeUnit::Nanosecond, None),
+            LogicalType::TimestampTZ => Self::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            LogicalType::Date => Self::Date32,
+            LogicalType::Time => Self::Time64(TimeUnit::Microsecond),
+            LogicalType::Interval => Self::Interval(IntervalUnit::MonthDayNano),
+            LogicalType::Varchar | LogicalType::Uuid => Self::Utf8,
+            LogicalType::Blob => Self::Binary,
+            LogicalType::Enum(members) => {
+                let key = match members.len() {
+                    len if len <= u8::MAX as usize => Self::UInt8,
+                    len if len <= u16::MAX as usize => Self::UInt16,
+                    _ => Self::UInt32,
+                };
+                Self::Dictionary(Box::new(key), Box::new(Self::Utf8))
+            }
+            LogicalType::List(child) => Self::List(Arc::new(Field::new("l", Self::from(child.as_ref()), true))),
+            LogicalType::Array(child, size) => Self::FixedSizeList(
+                Arc::new(Field::new("", Self::from(child.as_ref()), true)),
+                (*size).try_into().expect("array size should fit in an i32"),
+            ),
+            LogicalType::Map(key, value) => {
+                let entries = Fields::from(vec![
+                    Field::new("key", Self::from(key.as_ref()), false),
+                    Field::new("value", Self::from(value.as_ref()), true),
+                ]);
+                Self::Map(Arc::new(Field::new("entries", Self::Struct(entries), false)), false)
+            }
+            LogicalType::Struct(children) => Self::Struct(fields(children)),
+            LogicalType::Union(members) => {
+                let type_ids = 0..members.len() as i8;
+                Self::Union(
+                    UnionFields::new(type_ids, fields(members).iter().cloned()),
+                    UnionMode::Sparse,
+                )
+            }
+        }
+    }
+}
+
+impl Connection {
+    /// Register the logical type under its alias, so it can be used by name like a type created with `CREATE TYPE`
+    ///
+    /// ```rust
+    /// # use duckdb::{core::LogicalTypeHandle, Connection, Result};
+    /// fn register_mood(conn: &Connection) -> Result<()> {
+    ///     let mut mood = LogicalTypeHandle::enum_type(&["sad", "ok", "happy"])?;
+    ///     mood.set_alias("mood")?;
+    ///     conn.register_logical_type(&mood)?;
+    ///     conn.execute_batch("CREATE TABLE diary (entry VARCHAR, feeling mood)")
+    /// }
+    /// # register_mood(&Connection::open_in_memory().unwrap()).unwrap();
+    /// ```
+    ///
+    /// Fails if the type has no alias or if a type with the same name exists.
+    pub fn register_logical_type(&self, logical_type: &LogicalTypeHandle) -> Result<()> {
+        self.db.borrow_mut().register_logical_type(logical_type)
+    }
+}
+
+impl InnerConnection {
+    /// Register the given logical type with the current db
+    pub fn register_logical_type(&mut self, logical_type: &LogicalTypeHandle) -> Result<()> {
+        if logical_type.alias().is_none() {
+            return Err(Error::DuckDBFailure(
+                ffi::Error::new(DuckDBError),
+                Some("a logical type needs an alias to be registered".to_owned()),
+            ));
+        }
+        unsafe {
+            let rc = duckdb_register_logical_type(self.con, logical_type.ptr, std::ptr::null_mut());
+            if rc != DuckDBSuccess {
+                return Err(Error::DuckDBFailure(ffi::Error::new(rc), None));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::core::{LogicalTypeHandle, LogicalTypeId};
+    use arrow::datatypes::{DataType, TimeUnit};
+
+    use crate::{
+        core::{LogicalType, LogicalTypeHandle, LogicalTypeId},
+        types::Type,
+        Connection, Result,
+    };
 
     #[test]
     fn test_struct() {
@@ -331,6 +868,53 @@ mod test {
         assert_eq!(typ.child(0).id(), crate::core::LogicalTypeId::Boolean);
     }
 
+    #[test]
+    fn test_enum() {
+        let typ = LogicalTypeHandle::enum_type(&["red", "green", "blue"]).unwrap();
+
+        assert_eq!(typ.id(), LogicalTypeId::Enum);
+        assert_eq!(typ.enum_dictionary_size(), 3);
+        assert_eq!(typ.enum_dictionary_value(2), "blue");
+
+        assert!(matches!(
+            LogicalTypeHandle::enum_type(&["red", "gr\0een"]),
+            Err(crate::Error::NulError(_))
+        ));
+    }
+
+    #[test]
+    fn test_alias() {
+        let mut typ = LogicalTypeHandle::from(LogicalTypeId::Varchar);
+        assert_eq!(typ.alias(), None);
+
+        typ.set_alias("email").unwrap();
+        assert_eq!(typ.alias().as_deref(), Some("email"));
+        assert_eq!(typ.id(), LogicalTypeId::Varchar);
+
+        assert!(matches!(typ.set_alias("e\0mail"), Err(crate::Error::NulError(_))));
+        assert_eq!(typ.alias().as_deref(), Some("email"));
+    }
+
+    #[test]
+    fn test_register_logical_type() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let mut mood = LogicalTypeHandle::enum_type(&["sad", "ok", "happy"])?;
+        assert!(conn.register_logical_type(&mood).is_err());
+
+        mood.set_alias("mood")?;
+        conn.register_logical_type(&mood)?;
+        assert!(conn.register_logical_type(&mood).is_err());
+
+        let feeling: String = conn.query_row("SELECT 'happy'::mood::VARCHAR", [], |row| row.get(0))?;
+        assert_eq!(feeling, "happy");
+        assert!(conn.execute_batch("SELECT 'angry'::mood").is_err());
+
+        conn.execute_batch("CREATE TABLE diary (feeling mood); INSERT INTO diary VALUES ('ok'), ('sad')")?;
+        let index: i64 = conn.query_row("SELECT max(enum_code(feeling)) FROM diary", [], |row| row.get(0))?;
+        assert_eq!(index, 1);
+        Ok(())
+    }
+
     #[test]
     fn test_decimal() {
         let typ = LogicalTypeHandle::decimal(10, 2);
@@ -364,4 +948,159 @@ mod test {
         assert_eq!(typ.child_name(1), "world");
         assert_eq!(typ.child(1).id(), LogicalTypeId::Integer);
     }
+
+    #[test]
+    fn test_nested_accessors() {
+        let int = LogicalTypeHandle::from(LogicalTypeId::Integer);
+        let varchar = LogicalTypeHandle::from(LogicalTypeId::Varchar);
+
+        assert_eq!(
+            LogicalTypeHandle::list(&int).list_child_type().id(),
+            LogicalTypeId::Integer
+        );
+
+        let array = LogicalTypeHandle::array(&varchar, 3);
+        assert_eq!(array.id(), LogicalTypeId::Array);
+        assert_eq!(array.array_child_type().id(), LogicalTypeId::Varchar);
+        assert_eq!(array.array_size(), 3);
+
+        let map = LogicalTypeHandle::map(&varchar, &int);
+        assert_eq!(map.map_key_type().id(), LogicalTypeId::Varchar);
+        assert_eq!(map.map_value_type().id(), LogicalTypeId::Integer);
+
+        let color = LogicalTypeHandle::enum_type(&["red", "green"]).unwrap();
+        assert_eq!(color.enum_values(), ["red", "green"]);
+        assert_eq!(color.enum_internal_type(), LogicalTypeId::UTinyint);
+
+        assert_eq!(
+            LogicalTypeHandle::decimal(4, 1).decimal_internal_type(),
+            LogicalTypeId::Smallint
+        );
+        assert_eq!(
+            LogicalTypeHandle::decimal(18, 3).decimal_internal_type(),
+            LogicalTypeId::Bigint
+        );
+        assert_eq!(
+            LogicalTypeHandle::decimal(38, 10).decimal_internal_type(),
+            LogicalTypeId::Hugeint
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not a list")]
+    fn test_list_child_type_of_non_list() {
+        LogicalTypeHandle::from(LogicalTypeId::Integer).list_child_type();
+    }
+
+    fn nested_type() -> LogicalType {
+        LogicalType::Struct(vec![
+            ("id".to_owned(), LogicalType::Bigint),
+            ("price".to_owned(), LogicalType::Decimal { width: 12, scale: 2 }),
+            (
+                "tags".to_owned(),
+                LogicalType::Map(
+                    Box::new(LogicalType::Varchar),
+                    Box::new(LogicalType::List(Box::new(LogicalType::Date))),
+                ),
+            ),
+            (
+                "color".to_owned(),
+                LogicalType::Enum(vec!["red".to_owned(), "green".to_owned()]),
+            ),
+            ("point".to_owned(), LogicalType::Array(Box::new(LogicalType::Float), 2)),
+            (
+                "either".to_owned(),
+                LogicalType::Union(vec![
+                    ("n".to_owned(), LogicalType::Integer),
+                    ("s".to_owned(), LogicalType::Varchar),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_logical_type_roundtrip() -> Result<()> {
+        let logical_type = nested_type();
+        let handle = LogicalTypeHandle::try_from(&logical_type)?;
+        assert_eq!(handle.id(), LogicalTypeId::Struct);
+        assert_eq!(LogicalType::try_from(&handle)?, logical_type);
+
+        let bad_enum = LogicalType::List(Box::new(LogicalType::Enum(vec!["a\0b".to_owned()])));
+        assert!(matches!(
+            LogicalTypeHandle::try_from(&bad_enum),
+            Err(crate::Error::NulError(_))
+        ));
+        let bad_field = LogicalType::Struct(vec![("a\0b".to_owned(), LogicalType::Integer)]);
+        assert!(matches!(
+            LogicalTypeHandle::try_from(&bad_field),
+            Err(crate::Error::NulError(_))
+        ));
+        let bad_member = LogicalType::Union(vec![("a\0b".to_owned(), LogicalType::Integer)]);
+        assert!(matches!(
+            LogicalTypeHandle::try_from(&bad_member),
+            Err(crate::Error::NulError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_logical_types() {
+        for id in [
+            LogicalTypeId::UHugeint,
+            LogicalTypeId::TimeTZ,
+            LogicalTypeId::Bit,
+            LogicalTypeId::Varint,
+        ] {
+            let handle = LogicalTypeHandle::from(id);
+            assert_eq!(handle.id(), id);
+            assert!(LogicalType::try_from(&handle).is_err());
+            assert!(LogicalType::try_from(&LogicalTypeHandle::list(&handle)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_logical_type_of_query() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TYPE color AS ENUM ('red', 'green');
+             CREATE TABLE t (
+                 id BIGINT,
+                 price DECIMAL(12, 2),
+                 tags MAP(VARCHAR, DATE[]),
+                 color color,
+                 point FLOAT[2],
+                 either UNION(n INTEGER, s VARCHAR)
+             );",
+        )?;
+        let mut stmt = conn.prepare("SELECT t FROM t")?;
+        stmt.execute([])?;
+        let schema = stmt.schema();
+        let data_type = schema.field(0).data_type();
+        assert_eq!(data_type, &DataType::from(&nested_type()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_logical_type_to_type() {
+        let list = LogicalType::List(Box::new(LogicalType::TimestampTZ));
+        assert_eq!(Type::from(&list), Type::List(Box::new(Type::Timestamp)));
+        assert_eq!(
+            DataType::from(&list),
+            DataType::List(std::sync::Arc::new(arrow::datatypes::Field::new(
+                "l",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true
+            )))
+        );
+
+        let Type::Struct(fields) = Type::from(&nested_type()) else {
+            panic!("expected a struct")
+        };
+        assert_eq!(fields[1], ("price".to_owned(), Type::Decimal));
+        assert_eq!(
+            fields[2].1,
+            Type::Map(Box::new(Type::Text), Box::new(Type::List(Box::new(Type::Date32))))
+        );
+        assert_eq!(fields[4].1, Type::Array(Box::new(Type::Float), 2));
+    }
 }
@@ -1,11 +1,13 @@
 mod data_chunk;
 mod logical_type;
+mod reader;
 mod selection_vector;
 mod value;
 mod vector;
 
 pub use data_chunk::DataChunkHandle;
-pub use logical_type::{LogicalTypeHandle, LogicalTypeId};
+pub use logical_type::{LogicalType, LogicalTypeHandle, LogicalTypeId};
+pub use reader::{Blobs, Lists, Strings, Values, VectorPrimitive};
 pub use selection_vector::SelectionVector;
 pub use value::*;
 pub use vector::*;
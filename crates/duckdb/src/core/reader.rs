@@ -0,0 +1,497 @@
+use std::{borrow::Cow, marker::PhantomData, ops::Range, slice};
+
+use super::{DataChunkHandle, FlatVector, LogicalTypeId};
+use crate::{
+    ffi::{self, duckdb_list_entry, duckdb_list_vector_get_child, duckdb_list_vector_get_size, duckdb_string_t},
+    Error, Result,
+};
+
+/// A Rust type with the same layout as the values of a DuckDB vector, see [`DataChunkHandle::column`].
+///
+/// # Safety
+///
+/// Every value of a vector of [`VectorPrimitive::TYPE_ID`] must be a valid value of the implementing type.
+pub unsafe trait VectorPrimitive: Copy {
+    /// The type of the vectors holding this primitive.
+    const TYPE_ID: LogicalTypeId;
+}
+
+macro_rules! vector_primitive {
+    ($($ty:ty => $id:ident),* $(,)?) => {
+        $(
+            unsafe impl VectorPrimitive for $ty {
+                const TYPE_ID: LogicalTypeId = LogicalTypeId::$id;
+            }
+        )*
+    };
+}
+
+vector_primitive! {
+    bool => Boolean,
+    i8 => Tinyint,
+    i16 => Smallint,
+    i32 => Integer,
+    i64 => Bigint,
+    u8 => UTinyint,
+    u16 => USmallint,
+    u32 => UInteger,
+    u64 => UBigint,
+    f32 => Float,
+    f64 => Double,
+}
+
+fn check_type(vector: &FlatVector, expected: LogicalTypeId) -> Result<()> {
+    let actual = vector.logical_type().id();
+    if actual != expected {
+        return Err(Error::DuckDBFailure(
+            ffi::Error::new(ffi::DuckDBError),
+            Some(format!("Expected a {expected:?} vector, found {actual:?}")),
+        ));
+    }
+    Ok(())
+}
+
+/// The rows of a vector, with the validity of every row.
+///
+/// The rows are read as stored, so the vector must be flat. The C API can neither tell whether a vector is flat nor
+/// flatten it, which is why the functions creating the readers are `unsafe`.
+#[derive(Clone)]
+struct Rows<'a> {
+    vector: FlatVector,
+    range: Range<usize>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl Rows<'_> {
+    fn new(vector: FlatVector, len: usize) -> Result<Self> {
+        if len > vector.capacity() {
+            return Err(Error::DuckDBFailure(
+                ffi::Error::new(ffi::DuckDBError),
+                Some(format!(
+                    "Cannot read {len} rows of a vector with a capacity of {}",
+                    vector.capacity()
+                )),
+            ));
+        }
+        Ok(Self {
+            vector,
+            range: 0..len,
+            _marker: PhantomData,
+        })
+    }
+
+    fn is_valid(&self, row: usize) -> bool {
+        row < self.range.end && !self.vector.row_is_null(row as u64)
+    }
+}
+
+/// An iterator over the values of a vector of primitives, `None` for NULL rows.
+///
+/// Created by [`DataChunkHandle::column`] and [`FlatVector::values`].
+#[derive(Clone)]
+pub struct Values<'a, T> {
+    rows: Rows<'a>,
+    _marker: PhantomData<&'a [T]>,
+}
+
+impl<'a, T: VectorPrimitive> Values<'a, T> {
+    fn new(vector: FlatVector, len: usize) -> Result<Self> {
+        check_type(&vector, T::TYPE_ID)?;
+        Ok(Self {
+            rows: Rows::new(vector, len)?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the value of the given row, regardless of the position of the iterator.
+    pub fn get(&self, row: usize) -> Option<T> {
+        self.rows
+            .is_valid(row)
+            .then(|| self.rows.vector.as_slice_with_len::<T>(self.rows.range.end)[row])
+    }
+}
+
+impl<T: VectorPrimitive> Iterator for Values<'_, T> {
+    type Item = Option<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.range.next()?;
+        Some(self.get(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.range.size_hint()
+    }
+}
+
+impl<T: VectorPrimitive> ExactSizeIterator for Values<'_, T> {}
+
+/// An iterator over the values of a `VARCHAR` vector, `None` for NULL rows.
+///
+/// Created by [`DataChunkHandle::strings`] and [`FlatVector::strings`].
+#[derive(Clone)]
+pub struct Strings<'a> {
+    rows: Rows<'a>,
+}
+
+impl<'a> Strings<'a> {
+    fn new(vector: FlatVector, len: usize) -> Result<Self> {
+        check_type(&vector, LogicalTypeId::Varchar)?;
+        Ok(Self {
+            rows: Rows::new(vector, len)?,
+        })
+    }
+
+    /// Returns the value of the given row, regardless of the position of the iterator.
+    pub fn get(&self, row: usize) -> Option<Cow<'a, str>> {
+        self.rows
+            .is_valid(row)
+            .then(|| String::from_utf8_lossy(bytes_at(&self.rows, row)))
+    }
+}
+
+impl<'a> Iterator for Strings<'a> {
+    type Item = Option<Cow<'a, str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.range.next()?;
+        Some(self.get(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.range.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Strings<'_> {}
+
+/// An iterator over the values of a `BLOB` vector, `None` for NULL rows.
+///
+/// Created by [`DataChunkHandle::blobs`] and [`FlatVector::blobs`].
+#[derive(Clone)]
+pub struct Blobs<'a> {
+    rows: Rows<'a>,
+}
+
+impl<'a> Blobs<'a> {
+    fn new(vector: FlatVector, len: usize) -> Result<Self> {
+        check_type(&vector, LogicalTypeId::Blob)?;
+        Ok(Self {
+            rows: Rows::new(vector, len)?,
+        })
+    }
+
+    /// Returns the value of the given row, regardless of the position of the iterator.
+    pub fn get(&self, row: usize) -> Option<&'a [u8]> {
+        self.rows.is_valid(row).then(|| bytes_at(&self.rows, row))
+    }
+}
+
+impl<'a> Iterator for Blobs<'a> {
+    type Item = Option<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.range.next()?;
+        Some(self.get(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.range.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Blobs<'_> {}
+
+/// The bytes of a valid row, short strings are stored inline so they borrow the vector data for `'a`.
+fn bytes_at<'a>(rows: &Rows<'a>, row: usize) -> &'a [u8] {
+    let value: *const duckdb_string_t = unsafe { rows.vector.as_mut_ptr::<duckdb_string_t>().add(row) };
+    unsafe {
+        let inlined = &(*value).value.inlined;
+        let len = inlined.length as usize;
+        let data = if len <= inlined.inlined.len() {
+            inlined.inlined.as_ptr()
+        } else {
+            (*value).value.pointer.ptr.cast_const()
+        };
+        slice::from_raw_parts(data.cast(), len)
+    }
+}
+
+/// An iterator over the entries of a `LIST` vector, i.e. the range of the values of every row in the child vector,
+/// `None` for NULL rows.
+///
+/// Created by [`DataChunkHandle::lists`] and [`FlatVector::lists`], the child values are read with
+/// [`Lists::values`], [`Lists::strings`], [`Lists::blobs`] and [`Lists::lists`].
+#[derive(Clone)]
+pub struct Lists<'a> {
+    rows: Rows<'a>,
+}
+
+impl<'a> Lists<'a> {
+    fn new(vector: FlatVector, len: usize) -> Result<Self> {
+        check_type(&vector, LogicalTypeId::List)?;
+        Ok(Self {
+            rows: Rows::new(vector, len)?,
+        })
+    }
+
+    /// Returns the entry of the given row, regardless of the position of the iterator.
+    pub fn get(&self, row: usize) -> Option<Range<usize>> {
+        self.rows.is_valid(row).then(|| {
+            let entry = self
+                .rows
+                .vector
+                .as_slice_with_len::<duckdb_list_entry>(self.rows.range.end)[row];
+            entry.offset as usize..(entry.offset + entry.length) as usize
+        })
+    }
+
+    fn child(&self) -> (FlatVector, usize) {
+        let ptr = self.rows.vector.unowned_ptr();
+        unsafe {
+            let len = duckdb_list_vector_get_size(ptr) as usize;
+            (FlatVector::with_capacity(duckdb_list_vector_get_child(ptr), len), len)
+        }
+    }
+
+    /// Reads the child vector as primitives.
+    pub fn values<T: VectorPrimitive>(&self) -> Result<Values<'a, T>> {
+        let (child, len) = self.child();
+        Values::new(child, len)
+    }
+
+    /// Reads the child vector as strings.
+    pub fn strings(&self) -> Result<Strings<'a>> {
+        let (child, len) = self.child();
+        Strings::new(child, len)
+    }
+
+    /// Reads the child vector as blobs.
+    pub fn blobs(&self) -> Result<Blobs<'a>> {
+        let (child, len) = self.child();
+        Blobs::new(child, len)
+    }
+
+    /// Reads the child vector as lists.
+    pub fn lists(&self) -> Result<Lists<'a>> {
+        let (child, len) = self.child();
+        Lists::new(child, len)
+    }
+}
+
+impl Iterator for Lists<'_> {
+    type Item = Option<Range<usize>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.range.next()?;
+        Some(self.get(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.range.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Lists<'_> {}
+
+impl FlatVector {
+    /// Reads the first `len` rows of a vector of primitives, checking the type of the vector.
+    ///
+    /// Fails if `len` exceeds the capacity of the vector.
+    ///
+    /// # Safety
+    ///
+    /// The vector must be flat, and so must the child vectors of a list. DuckDB flattens the input of functions and
+    /// the chunks of query results. A vector made a dictionary or constant vector, e.g. by
+    /// [`FlatVector::slice`], [`FlatVector::set_dictionary`] or [`FlatVector::assign_to_constant`] through any handle,
+    /// stores fewer rows than it has, so reading it reads out of bounds.
+    pub unsafe fn values<T: VectorPrimitive>(&self, len: usize) -> Result<Values<'_, T>> {
+        Values::new(self.clone(), len)
+    }
+
+    /// Reads the first `len` rows of a `VARCHAR` vector.
+    ///
+    /// # Safety
+    ///
+    /// The vector must be flat, see [`FlatVector::values`].
+    pub unsafe fn strings(&self, len: usize) -> Result<Strings<'_>> {
+        Strings::new(self.clone(), len)
+    }
+
+    /// Reads the first `len` rows of a `BLOB` vector.
+    ///
+    /// # Safety
+    ///
+    /// The vector must be flat, see [`FlatVector::values`].
+    pub unsafe fn blobs(&self, len: usize) -> Result<Blobs<'_>> {
+        Blobs::new(self.clone(), len)
+    }
+
+    /// Reads the first `len` rows of a `LIST` vector.
+    ///
+    /// # Safety
+    ///
+    /// The vector and its child vector must be flat, see [`FlatVector::values`].
+    pub unsafe fn lists(&self, len: usize) -> Result<Lists<'_>> {
+        Lists::new(self.clone(), len)
+    }
+}
+
+impl DataChunkHandle {
+    fn checked_vector(&self, idx: usize) -> Result<FlatVector> {
+        if idx >= self.num_columns() {
+            return Err(Error::InvalidColumnIndex(idx));
+        }
+        Ok(self.flat_vector(idx))
+    }
+
+    /// Reads the column at the index as primitives, checking the type of the column.
+    ///
+    /// ```rust
+    /// # use duckdb::core::{DataChunkHandle, LogicalTypeId};
+    /// let chunk = DataChunkHandle::new(&[LogicalTypeId::Bigint.into()]);
+    /// chunk.flat_vector(0).copy(&[1i64, 2]);
+    /// chunk.set_len(2);
+    /// // SAFETY: the column was written row by row
+    /// let values = unsafe { chunk.column::<i64>(0) };
+    /// assert_eq!(values.unwrap().collect::<Vec<_>>(), [Some(1), Some(2)]);
+    /// assert!(unsafe { chunk.column::<i32>(0) }.is_err());
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The column must be flat, see [`FlatVector::values`]. The input of a scalar function always is.
+    pub unsafe fn column<T: VectorPrimitive>(&self, idx: usize) -> Result<Values<'_, T>> {
+        Values::new(self.checked_vector(idx)?, self.len())
+    }
+
+    /// Reads the `VARCHAR` column at the index.
+    ///
+    /// # Safety
+    ///
+    /// The column must be flat, see [`FlatVector::values`].
+    pub unsafe fn strings(&self, idx: usize) -> Result<Strings<'_>> {
+        Strings::new(self.checked_vector(idx)?, self.len())
+    }
+
+    /// Reads the `BLOB` column at the index.
+    ///
+    /// # Safety
+    ///
+    /// The column must be flat, see [`FlatVector::values`].
+    pub unsafe fn blobs(&self, idx: usize) -> Result<Blobs<'_>> {
+        Blobs::new(self.checked_vector(idx)?, self.len())
+    }
+
+    /// Reads the `LIST` column at the index.
+    ///
+    /// # Safety
+    ///
+    /// The column and its child vector must be flat, see [`FlatVector::values`].
+    pub unsafe fn lists(&self, idx: usize) -> Result<Lists<'_>> {
+        Lists::new(self.checked_vector(idx)?, self.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{DataChunkHandle, Inserter, LogicalTypeHandle, LogicalTypeId},
+        Error, Result,
+    };
+
+    #[test]
+    fn test_read_columns() -> Result<()> {
+        let chunk = DataChunkHandle::new(&[
+            LogicalTypeId::Integer.into(),
+            LogicalTypeId::Varchar.into(),
+            LogicalTypeId::Blob.into(),
+        ]);
+        let mut ints = chunk.flat_vector(0);
+        ints.copy(&[1, 2, 3]);
+        ints.set_null(1);
+        let strings = chunk.flat_vector(1);
+        strings.insert(0, "short");
+        strings.insert(2, "a string that is too long to be inlined");
+        chunk.flat_vector(1).set_null(1);
+        let blobs = chunk.flat_vector(2);
+        blobs.insert(0, b"\x00\x01".as_slice());
+        blobs.insert(1, b"".as_slice());
+        chunk.flat_vector(2).set_null(2);
+        chunk.set_len(3);
+
+        let values = unsafe { chunk.column::<i32>(0) }?;
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.get(2), Some(3));
+        assert_eq!(values.collect::<Vec<_>>(), [Some(1), None, Some(3)]);
+
+        let strings = unsafe { chunk.strings(1) }?
+            .map(|s| s.map(|s| s.into_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            strings,
+            [
+                Some("short".to_string()),
+                None,
+                Some("a string that is too long to be inlined".to_string())
+            ]
+        );
+        assert_eq!(
+            unsafe { chunk.blobs(2) }?.collect::<Vec<_>>(),
+            [Some(b"\x00\x01".as_slice()), Some(b"".as_slice()), None]
+        );
+
+        assert!(unsafe { chunk.column::<i64>(0) }.is_err());
+        assert!(unsafe { chunk.strings(2) }.is_err());
+        assert!(matches!(
+            unsafe { chunk.column::<i32>(3) },
+            Err(Error::InvalidColumnIndex(3))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_lists() -> Result<()> {
+        let list_type = LogicalTypeHandle::list(&LogicalTypeId::Bigint.into());
+        let chunk = DataChunkHandle::new(&[list_type]);
+        let mut list = chunk.list_vector(0);
+        list.set_child(&[1i64, 2, 3]);
+        list.set_entry(0, 0, 2);
+        list.set_null(1);
+        list.set_entry(2, 2, 1);
+        chunk.set_len(3);
+
+        let lists = unsafe { chunk.lists(0) }?;
+        let values = lists.values::<i64>()?;
+        assert!(lists.strings().is_err());
+        let sums = lists
+            .map(|entry| entry.map(|range| range.map(|i| values.get(i).unwrap()).sum::<i64>()))
+            .collect::<Vec<_>>();
+        assert_eq!(sums, [Some(3), None, Some(3)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_checks_capacity() {
+        let chunk = DataChunkHandle::new(&[LogicalTypeId::Integer.into()]);
+        let vector = chunk.flat_vector(0);
+        assert!(unsafe { vector.values::<i32>(vector.capacity()) }.is_ok());
+        assert!(unsafe { vector.values::<i32>(vector.capacity() + 1) }.is_err());
+    }
+
+    #[test]
+    fn test_read_long_list() -> Result<()> {
+        // the child of a list may hold more rows than a vector
+        let list_type = LogicalTypeHandle::list(&LogicalTypeId::Integer.into());
+        let chunk = DataChunkHandle::new(&[list_type]);
+        let mut list = chunk.list_vector(0);
+        let values = (0..5000).collect::<Vec<i32>>();
+        list.set_child(&values);
+        list.set_entry(0, 0, values.len());
+        chunk.set_len(1);
+
+        let lists = unsafe { chunk.lists(0) }?;
+        assert_eq!(lists.values::<i32>()?.get(4999), Some(4999));
+        Ok(())
+    }
+}
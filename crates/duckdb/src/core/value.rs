@@ -1,13 +1,10 @@
-use crate::ffi::{duckdb_destroy_value, duckdb_get_int64, duckdb_get_varchar, duckdb_value};
-use libduckdb_sys::{
-    duckdb_create_blob, duckdb_create_bool, duckdb_create_date, duckdb_create_decimal, duckdb_create_double,
-    duckdb_create_float, duckdb_create_int16, duckdb_create_int32, duckdb_create_int64, duckdb_create_int8,
-    duckdb_create_null_value, duckdb_create_time, duckdb_create_timestamp, duckdb_create_timestamp_ms,
-    duckdb_create_timestamp_ns, duckdb_create_timestamp_s, duckdb_create_uint16, duckdb_create_uint32,
-    duckdb_create_uint64, duckdb_create_uint8, duckdb_create_varchar_length, duckdb_date, duckdb_decimal, duckdb_time,
-    duckdb_timestamp, duckdb_timestamp_ms, duckdb_timestamp_ns, duckdb_timestamp_s,
+use super::{LogicalType, LogicalTypeHandle};
+use crate::{
+    ffi::{self, *},
+    types::{self, OrderedMap, TimeUnit},
+    Error, Result,
 };
-use std::{ffi::CString, fmt};
+use std::{ffi::CStr, fmt};
 
 /// The Value object holds a single arbitrary value of any type that can be
 /// stored in the database.
@@ -70,6 +67,72 @@ impl Value {
             ptr: unsafe { duckdb_create_timestamp_ns(duckdb_timestamp_ns { nanos }) },
         }
     }
+
+    /// A `TIMESTAMP WITH TIME ZONE` of microseconds since the epoch
+    pub fn timestamp_tz(micros: i64) -> Value {
+        Self {
+            ptr: unsafe { duckdb_create_timestamp_tz(duckdb_timestamp { micros }) },
+        }
+    }
+
+    /// An `INTERVAL` of `months`, `days` and `micros`, the parts are kept apart as a month has no fixed number of days
+    pub fn interval(months: i32, days: i32, micros: i64) -> Value {
+        Self {
+            ptr: unsafe { duckdb_create_interval(duckdb_interval { months, days, micros }) },
+        }
+    }
+
+    /// A `UUID` from its 128 bits, most significant first
+    pub fn uuid(value: u128) -> Value {
+        let value = duckdb_uhugeint {
+            lower: value as u64,
+            upper: (value >> 64) as u64,
+        };
+        Self {
+            ptr: unsafe { duckdb_create_uuid(value) },
+        }
+    }
+
+    /// A `LIST` of `values`, which must all be of the `element` type or `NULL`
+    pub fn list(element: &LogicalTypeHandle, values: &[Value]) -> Result<Value> {
+        let mut ptrs: Vec<duckdb_value> = values.iter().map(|value| value.ptr).collect();
+        let ptr = unsafe { duckdb_create_list_value(element.ptr, ptrs.as_mut_ptr(), ptrs.len() as idx_t) };
+        Self::created(ptr, "LIST")
+    }
+
+    /// An `ARRAY` of `values`, which must all be of the `element` type or `NULL`
+    pub fn array(element: &LogicalTypeHandle, values: &[Value]) -> Result<Value> {
+        let mut ptrs: Vec<duckdb_value> = values.iter().map(|value| value.ptr).collect();
+        let ptr = unsafe { duckdb_create_array_value(element.ptr, ptrs.as_mut_ptr(), ptrs.len() as idx_t) };
+        Self::created(ptr, "ARRAY")
+    }
+
+    /// A `STRUCT` of the given struct type with one value per field
+    pub fn struct_value(logical_type: &LogicalTypeHandle, values: &[Value]) -> Result<Value> {
+        if logical_type.id() != super::LogicalTypeId::Struct || logical_type.num_children() != values.len() {
+            return Err(invalid_value("a STRUCT needs a struct type with one field per value"));
+        }
+        let mut ptrs: Vec<duckdb_value> = values.iter().map(|value| value.ptr).collect();
+        let ptr = unsafe { duckdb_create_struct_value(logical_type.ptr, ptrs.as_mut_ptr()) };
+        Self::created(ptr, "STRUCT")
+    }
+
+    /// The member at `index` of the given enum type
+    pub fn enum_value(logical_type: &LogicalTypeHandle, index: u64) -> Result<Value> {
+        let ptr = unsafe { duckdb_create_enum_value(logical_type.ptr, index) };
+        Self::created(ptr, "ENUM")
+    }
+
+    fn created(ptr: duckdb_value, kind: &str) -> Result<Value> {
+        if ptr.is_null() {
+            return Err(invalid_value(&format!("cannot create the {kind} value")));
+        }
+        Ok(Self { ptr })
+    }
+}
+
+fn invalid_value(message: &str) -> Error {
+    Error::DuckDBFailure(ffi::Error::new(DuckDBError), Some(message.to_owned()))
 }
 
 impl From<duckdb_value> for Value {
@@ -119,17 +182,197 @@ impl Drop for Value {
     }
 }
 
+/// The getters cast the value to the requested type the way DuckDB does, a value that cannot be cast is returned as
+/// the type's default.
 impl Value {
+    /// Returns true if the value is `NULL`
+    pub fn is_null(&self) -> bool {
+        unsafe { duckdb_is_null_value(self.ptr) }
+    }
+
+    /// Returns the type of the value
+    ///
+    /// Fails for types [`LogicalType`] has no variant for, see [`LogicalType::try_from`].
+    pub fn logical_type(&self) -> Result<LogicalType> {
+        let handle = unsafe { LogicalTypeHandle::new_unowned(duckdb_get_value_type(self.ptr)) };
+        LogicalType::try_from(&handle)
+    }
+
+    /// Returns the value as a bool
+    pub fn to_bool(&self) -> bool {
+        unsafe { duckdb_get_bool(self.ptr) }
+    }
+
+    /// Returns the value as a int8
+    pub fn to_int8(&self) -> i8 {
+        unsafe { duckdb_get_int8(self.ptr) }
+    }
+
+    /// Returns the value as a int16
+    pub fn to_int16(&self) -> i16 {
+        unsafe { duckdb_get_int16(self.ptr) }
+    }
+
+    /// Returns the value as a int32
+    pub fn to_int32(&self) -> i32 {
+        unsafe { duckdb_get_int32(self.ptr) }
+    }
+
     /// Returns the value as a int64
     pub fn to_int64(&self) -> i64 {
         unsafe { duckdb_get_int64(self.ptr) }
     }
+
+    /// Returns the value as a hugeint
+    pub fn to_hugeint(&self) -> i128 {
+        let value = unsafe { duckdb_get_hugeint(self.ptr) };
+        ((value.upper as i128) << 64) | value.lower as i128
+    }
+
+    /// Returns the value as a uint8
+    pub fn to_uint8(&self) -> u8 {
+        unsafe { duckdb_get_uint8(self.ptr) }
+    }
+
+    /// Returns the value as a uint16
+    pub fn to_uint16(&self) -> u16 {
+        unsafe { duckdb_get_uint16(self.ptr) }
+    }
+
+    /// Returns the value as a uint32
+    pub fn to_uint32(&self) -> u32 {
+        unsafe { duckdb_get_uint32(self.ptr) }
+    }
+
+    /// Returns the value as a uint64
+    pub fn to_uint64(&self) -> u64 {
+        unsafe { duckdb_get_uint64(self.ptr) }
+    }
+
+    /// Returns the value as a float
+    pub fn to_float(&self) -> f32 {
+        unsafe { duckdb_get_float(self.ptr) }
+    }
+
+    /// Returns the value as a double
+    pub fn to_double(&self) -> f64 {
+        unsafe { duckdb_get_double(self.ptr) }
+    }
+
+    /// Returns the width, scale and unscaled value of a decimal, all zero if the value is not a decimal
+    pub fn to_decimal(&self) -> duckdb_decimal {
+        unsafe { duckdb_get_decimal(self.ptr) }
+    }
+
+    /// Returns the value as a date, in days since the epoch
+    pub fn to_date(&self) -> i32 {
+        unsafe { duckdb_get_date(self.ptr).days }
+    }
+
+    /// Returns the value as a time, in microseconds since midnight
+    pub fn to_time(&self) -> i64 {
+        unsafe { duckdb_get_time(self.ptr).micros }
+    }
+
+    /// Returns the value as a timestamp, in seconds since the epoch
+    pub fn to_timestamp_s(&self) -> i64 {
+        unsafe { duckdb_get_timestamp_s(self.ptr).seconds }
+    }
+
+    /// Returns the value as a timestamp, in milliseconds since the epoch
+    pub fn to_timestamp_ms(&self) -> i64 {
+        unsafe { duckdb_get_timestamp_ms(self.ptr).millis }
+    }
+
+    /// Returns the value as a timestamp, in microseconds since the epoch
+    pub fn to_timestamp_us(&self) -> i64 {
+        unsafe { duckdb_get_timestamp(self.ptr).micros }
+    }
+
+    /// Returns the value as a timestamp, in nanoseconds since the epoch
+    pub fn to_timestamp_ns(&self) -> i64 {
+        unsafe { duckdb_get_timestamp_ns(self.ptr).nanos }
+    }
+
+    /// Returns the value as a timestamp with time zone, in microseconds since the epoch
+    pub fn to_timestamp_tz(&self) -> i64 {
+        unsafe { duckdb_get_timestamp_tz(self.ptr).micros }
+    }
+
+    /// Returns the value as an interval
+    pub fn to_interval(&self) -> duckdb_interval {
+        unsafe { duckdb_get_interval(self.ptr) }
+    }
+
+    /// Returns the value as a uuid, most significant bits first
+    pub fn to_uuid(&self) -> u128 {
+        let value = unsafe { duckdb_get_uuid(self.ptr) };
+        ((value.upper as u128) << 64) | value.lower as u128
+    }
+
+    /// Returns the value as a blob
+    pub fn to_blob(&self) -> Vec<u8> {
+        unsafe {
+            let blob = duckdb_get_blob(self.ptr);
+            let bytes = std::slice::from_raw_parts(blob.data as *const u8, blob.size as usize).to_vec();
+            duckdb_free(blob.data);
+            bytes
+        }
+    }
+
+    /// Returns the value as a string
+    pub fn to_varchar(&self) -> String {
+        unsafe {
+            let varchar = duckdb_get_varchar(self.ptr);
+            let string = CStr::from_ptr(varchar).to_string_lossy().into_owned();
+            duckdb_free(varchar.cast());
+            string
+        }
+    }
+
+    /// Returns the index of the member of an enum
+    pub fn to_enum_index(&self) -> u64 {
+        unsafe { duckdb_get_enum_value(self.ptr) }
+    }
+
+    /// Returns the number of elements of a list, 0 if the value is not a list
+    pub fn list_size(&self) -> usize {
+        unsafe { duckdb_get_list_size(self.ptr) as usize }
+    }
+
+    /// Returns the element of a list at `idx`
+    pub fn list_child(&self, idx: usize) -> Option<Value> {
+        Self::child(unsafe { duckdb_get_list_child(self.ptr, idx as idx_t) })
+    }
+
+    /// Returns the field of a struct at `idx`
+    pub fn struct_child(&self, idx: usize) -> Option<Value> {
+        Self::child(unsafe { duckdb_get_struct_child(self.ptr, idx as idx_t) })
+    }
+
+    /// Returns the number of entries of a map, 0 if the value is not a map
+    pub fn map_size(&self) -> usize {
+        unsafe { duckdb_get_map_size(self.ptr) as usize }
+    }
+
+    /// Returns the key of the map entry at `idx`
+    pub fn map_key(&self, idx: usize) -> Option<Value> {
+        Self::child(unsafe { duckdb_get_map_key(self.ptr, idx as idx_t) })
+    }
+
+    /// Returns the value of the map entry at `idx`
+    pub fn map_value(&self, idx: usize) -> Option<Value> {
+        Self::child(unsafe { duckdb_get_map_value(self.ptr, idx as idx_t) })
+    }
+
+    fn child(ptr: duckdb_value) -> Option<Value> {
+        (!ptr.is_null()).then_some(Self { ptr })
+    }
 }
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let c_string = unsafe { CString::from_raw(duckdb_get_varchar(self.ptr)) };
-        write!(f, "{}", c_string.to_str().expect("cannot extract c_str"))
+        write!(f, "{}", self.to_varchar())
     }
 }
 
@@ -156,3 +399,259 @@ impl_duckdb_create_value!(u32, duckdb_create_uint32);
 impl_duckdb_create_value!(u64, duckdb_create_uint64);
 impl_duckdb_create_value!(f32, duckdb_create_float);
 impl_duckdb_create_value!(f64, duckdb_create_double);
+
+impl From<i128> for Value {
+    fn from(value: i128) -> Self {
+        let value = duckdb_hugeint {
+            lower: value as u64,
+            upper: (value >> 64) as i64,
+        };
+        Value {
+            ptr: unsafe { duckdb_create_hugeint(value) },
+        }
+    }
+}
+
+impl TryFrom<&Value> for types::Value {
+    type Error = Error;
+
+    /// Reads the value and all of its children
+    ///
+    /// [`types::Value`] has no variant for some types, so their type is lost:
+    /// - a `UUID` is read as [`types::Value::Text`] of its canonical form,
+    /// - a `TIMESTAMP WITH TIME ZONE` is read as a [`types::Value::Timestamp`] in microseconds since the epoch in UTC.
+    ///
+    /// `ARRAY` and `UNION` values cannot be read as the C API does not expose their children.
+    fn try_from(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            return Ok(Self::Null);
+        }
+        let children = |len: usize, child: fn(&Value, usize) -> Option<Value>| -> Result<Vec<Self>> {
+            (0..len)
+                .map(|idx| Self::try_from(&child(value, idx).expect("index should be in range")))
+                .collect()
+        };
+        Ok(match value.logical_type()? {
+            LogicalType::SQLNull => Self::Null,
+            LogicalType::Boolean => Self::Boolean(value.to_bool()),
+            LogicalType::Tinyint => Self::TinyInt(value.to_int8()),
+            LogicalType::Smallint => Self::SmallInt(value.to_int16()),
+            LogicalType::Integer => Self::Int(value.to_int32()),
+            LogicalType::Bigint => Self::BigInt(value.to_int64()),
+            LogicalType::Hugeint => Self::HugeInt(value.to_hugeint()),
+            LogicalType::UTinyint => Self::UTinyInt(value.to_uint8()),
+            LogicalType::USmallint => Self::USmallInt(value.to_uint16()),
+            LogicalType::UInteger => Self::UInt(value.to_uint32()),
+            LogicalType::UBigint => Self::UBigInt(value.to_uint64()),
+            LogicalType::Float => Self::Float(value.to_float()),
+            LogicalType::Double => Self::Double(value.to_double()),
+            LogicalType::Decimal { .. } => Self::Decimal(value.to_decimal().into()),
+            LogicalType::Timestamp | LogicalType::TimestampTZ => {
+                Self::Timestamp(TimeUnit::Microsecond, value.to_timestamp_us())
+            }
+            LogicalType::TimestampS => Self::Timestamp(TimeUnit::Second, value.to_timestamp_s()),
+            LogicalType::TimestampMs => Self::Timestamp(TimeUnit::Millisecond, value.to_timestamp_ms()),
+            LogicalType::TimestampNs => Self::Timestamp(TimeUnit::Nanosecond, value.to_timestamp_ns()),
+            LogicalType::Date => Self::Date32(value.to_date()),
+            LogicalType::Time => Self::Time64(TimeUnit::Microsecond, value.to_time()),
+            LogicalType::Interval => {
+                let interval = value.to_interval();
+                Self::Interval {
+                    months: interval.months,
+                    days: interval.days,
+                    nanos: interval.micros * 1000,
+                }
+            }
+            LogicalType::Varchar | LogicalType::Uuid => Self::Text(value.to_varchar()),
+            LogicalType::Blob => Self::Blob(value.to_blob()),
+            LogicalType::Enum(members) => Self::Enum(members[value.to_enum_index() as usize].clone()),
+            LogicalType::List(_) => Self::List(children(value.list_size(), Value::list_child)?),
+            LogicalType::Struct(fields) => {
+                let values = children(fields.len(), Value::struct_child)?;
+                let names = fields.into_iter().map(|(name, _)| name);
+                Self::Struct(OrderedMap::from(names.zip(values).collect::<Vec<_>>()))
+            }
+            LogicalType::Map(..) => {
+                let keys = children(value.map_size(), Value::map_key)?;
+                let values = children(value.map_size(), Value::map_value)?;
+                Self::Map(OrderedMap::from(keys.into_iter().zip(values).collect::<Vec<_>>()))
+            }
+            logical_type @ (LogicalType::Array(..) | LogicalType::Union(_)) => {
+                return Err(invalid_value(&format!("cannot read the {:?} value", logical_type.id())));
+            }
+        })
+    }
+}
+
+impl TryFrom<&types::Value> for Value {
+    type Error = Error;
+
+    /// Creates the value and all of its children
+    ///
+    /// The value is not always created as the type it was read from:
+    /// - a [`types::Value::Enum`] is created as a `VARCHAR` of its member, as its `ENUM` type is not known,
+    /// - the nanoseconds of a [`types::Value::Time64`] and a [`types::Value::Interval`] are truncated to
+    ///   microseconds, the precision of `TIME` and `INTERVAL`,
+    /// - the element type of a [`types::Value::List`] or [`types::Value::Array`] is the type of its first non-`NULL`
+    ///   element, and creating the value fails if the other elements have another type, e.g. a `TinyInt` followed
+    ///   by an `Int`.
+    ///
+    /// `MAP` and `UNION` values cannot be created as the C API does not support them.
+    fn try_from(value: &types::Value) -> Result<Self> {
+        let elements = |values: &[types::Value]| -> Result<(LogicalTypeHandle, Vec<Value>)> {
+            let values = values.iter().map(Self::try_from).collect::<Result<Vec<_>>>()?;
+            let element = values
+                .iter()
+                .find(|value| !value.is_null())
+                .map_or(Ok(LogicalType::SQLNull), Value::logical_type)?;
+            Ok((LogicalTypeHandle::try_from(&element)?, values))
+        };
+        Ok(match value {
+            types::Value::Null => Self::null(),
+            types::Value::Boolean(value) => Self::from(*value),
+            types::Value::TinyInt(value) => Self::from(*value),
+            types::Value::SmallInt(value) => Self::from(*value),
+            types::Value::Int(value) => Self::from(*value),
+            types::Value::BigInt(value) => Self::from(*value),
+            types::Value::HugeInt(value) => Self::from(*value),
+            types::Value::UTinyInt(value) => Self::from(*value),
+            types::Value::USmallInt(value) => Self::from(*value),
+            types::Value::UInt(value) => Self::from(*value),
+            types::Value::UBigInt(value) => Self::from(*value),
+            types::Value::Float(value) => Self::from(*value),
+            types::Value::Double(value) => Self::from(*value),
+            types::Value::Decimal(value) => Self::from(duckdb_decimal::from(value.checked()?)),
+            types::Value::Timestamp(unit, value) => match unit {
+                TimeUnit::Second => Self::timestamp_s(*value),
+                TimeUnit::Millisecond => Self::timestamp_ms(*value),
+                TimeUnit::Microsecond => Self::timestamp_us(*value),
+                TimeUnit::Nanosecond => Self::timestamp_ns(*value),
+            },
+            types::Value::Text(value) | types::Value::Enum(value) => Self::from(value.as_str()),
+            types::Value::Blob(value) => Self::from(value.as_slice()),
+            types::Value::Date32(value) => Self::date_from_day_count(*value),
+            types::Value::Time64(unit, value) => Self::time_from_us(unit.to_micros(*value)),
+            types::Value::Interval { months, days, nanos } => Self::interval(*months, *days, nanos / 1000),
+            types::Value::List(values) => {
+                let (element, values) = elements(values)?;
+                Self::list(&element, &values)?
+            }
+            types::Value::Array(values) => {
+                let (element, values) = elements(values)?;
+                Self::array(&element, &values)?
+            }
+            types::Value::Struct(fields) => {
+                let values = fields.values().map(Self::try_from).collect::<Result<Vec<_>>>()?;
+                let types = fields
+                    .keys()
+                    .zip(&values)
+                    .map(|(name, value)| Ok((name.as_str(), LogicalTypeHandle::try_from(&value.logical_type()?)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                Self::struct_value(&LogicalTypeHandle::try_struct_type(&types)?, &values)?
+            }
+            types::Value::Map(_) | types::Value::Union(_) => {
+                return Err(invalid_value("MAP and UNION values cannot be created"));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Value;
+    use crate::{
+        core::{LogicalType, LogicalTypeHandle, LogicalTypeId},
+        types::{self, Decimal128, OrderedMap, TimeUnit},
+        Result,
+    };
+
+    #[test]
+    fn test_getters() {
+        assert!(Value::null().is_null());
+        assert_eq!(Value::from(true).logical_type().unwrap(), LogicalType::Boolean);
+        assert_eq!(Value::from(-3i8).to_int8(), -3);
+        assert_eq!(Value::from(u64::MAX).to_uint64(), u64::MAX);
+        assert_eq!(Value::from(i128::MIN).to_hugeint(), i128::MIN);
+        assert_eq!(Value::from(1.5f64).to_double(), 1.5);
+        assert_eq!(Value::from("duck").to_varchar(), "duck");
+        assert_eq!(Value::from(&b"\x00\xff"[..]).to_blob(), b"\x00\xff");
+        assert_eq!(Value::timestamp_tz(42).to_timestamp_tz(), 42);
+        assert_eq!(Value::interval(1, 2, 3).to_interval().micros, 3);
+
+        let uuid = 0x0123_4567_89ab_cdef_0123_4567_89ab_cdef;
+        assert_eq!(Value::uuid(uuid).to_uuid(), uuid);
+        assert_eq!(Value::uuid(uuid).to_varchar(), "01234567-89ab-cdef-0123-456789abcdef");
+    }
+
+    #[test]
+    fn test_nested_values() -> Result<()> {
+        let int = LogicalTypeHandle::from(LogicalTypeId::Integer);
+        let list = Value::list(&int, &[Value::from(1), Value::null(), Value::from(3)])?;
+        assert_eq!(list.logical_type()?, LogicalType::List(Box::new(LogicalType::Integer)));
+        assert_eq!(list.list_size(), 3);
+        assert!(list.list_child(1).unwrap().is_null());
+        assert_eq!(list.list_child(2).unwrap().to_int32(), 3);
+        assert!(list.list_child(3).is_none());
+        assert!(Value::list(&int, &[Value::from("duck")]).is_err());
+
+        let point =
+            LogicalTypeHandle::struct_type(&[("x", int), ("y", LogicalTypeHandle::from(LogicalTypeId::Varchar))]);
+        let value = Value::struct_value(&point, &[Value::from(1), Value::from("a")])?;
+        assert_eq!(value.struct_child(1).unwrap().to_varchar(), "a");
+        assert!(Value::struct_value(&point, &[Value::from(1)]).is_err());
+
+        let color = LogicalTypeHandle::enum_type(&["red", "green"])?;
+        assert_eq!(Value::enum_value(&color, 1)?.to_enum_index(), 1);
+        assert!(Value::enum_value(&color, 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_values() -> Result<()> {
+        let values = [
+            types::Value::Null,
+            types::Value::HugeInt(-(1 << 100)),
+            types::Value::Decimal(Decimal128::new(-12345, 9, 3)),
+            types::Value::Timestamp(TimeUnit::Nanosecond, 1),
+            types::Value::Time64(TimeUnit::Microsecond, 7),
+            types::Value::Interval {
+                months: 1,
+                days: 2,
+                nanos: 3000,
+            },
+            types::Value::Blob(vec![0, 1]),
+            types::Value::List(vec![types::Value::Null, types::Value::Text("a".to_owned())]),
+            types::Value::List(vec![]),
+            types::Value::Struct(OrderedMap::from(vec![
+                ("id".to_owned(), types::Value::UInt(7)),
+                (
+                    "tags".to_owned(),
+                    types::Value::List(vec![types::Value::List(vec![types::Value::Boolean(true)])]),
+                ),
+            ])),
+        ];
+        for value in values {
+            let converted = Value::try_from(&value)?;
+            assert_eq!(types::Value::try_from(&converted)?, value);
+        }
+
+        let color = LogicalTypeHandle::enum_type(&["red", "green"])?;
+        let value = Value::enum_value(&color, 1)?;
+        assert_eq!(types::Value::try_from(&value)?, types::Value::Enum("green".to_owned()));
+
+        // the lossy cases
+        let time = Value::try_from(&types::Value::Time64(TimeUnit::Nanosecond, 1_999))?;
+        assert_eq!(
+            types::Value::try_from(&time)?,
+            types::Value::Time64(TimeUnit::Microsecond, 1)
+        );
+        let member = Value::try_from(&types::Value::Enum("green".to_owned()))?;
+        assert_eq!(types::Value::try_from(&member)?, types::Value::Text("green".to_owned()));
+        let mixed = types::Value::List(vec![types::Value::TinyInt(1), types::Value::Int(300)]);
+        assert!(Value::try_from(&mixed).is_err());
+
+        let map = types::Value::Map(OrderedMap::from(vec![(types::Value::Int(1), types::Value::Int(2))]));
+        assert!(Value::try_from(&map).is_err());
+        Ok(())
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
         duckdb_create_vector, duckdb_destroy_vector, duckdb_list_entry, duckdb_list_vector_get_child,
         duckdb_list_vector_get_size, duckdb_list_vector_reserve, duckdb_list_vector_set_size,
         duckdb_set_dictionary_vector_id, duckdb_slice_vector, duckdb_struct_type_child_count,
-        duckdb_struct_type_child_name, duckdb_struct_vector_get_child, duckdb_validity_set_row_invalid, duckdb_vector,
+        duckdb_struct_type_child_name, duckdb_struct_vector_get_child, duckdb_union_type_member_count,
+        duckdb_union_type_member_name, duckdb_validity_set_row_invalid, duckdb_vector,
         duckdb_vector_assign_string_element, duckdb_vector_assign_string_element_len,
         duckdb_vector_ensure_validity_writable, duckdb_vector_get_column_type, duckdb_vector_get_data,
         duckdb_vector_get_validity, duckdb_vector_reference_value, duckdb_vector_reference_vector, duckdb_vector_size,
@@ -70,7 +71,7 @@ impl Drop for FlatVector {
 }
 
 impl FlatVector {
-    fn with_capacity(ptr: duckdb_vector, capacity: usize) -> Self {
+    pub(super) fn with_capacity(ptr: duckdb_vector, capacity: usize) -> Self {
         Self {
             ptr,
             capacity,
@@ -160,6 +161,9 @@ impl FlatVector {
         }
     }
 
+    /// Turn the vector into a dictionary vector whose rows select from its first `dict_len` rows.
+    ///
+    /// Row `i` of the vector becomes the row `selection_vector[i]` of the current values.
     pub fn slice(&mut self, dict_len: u64, selection_vector: SelectionVector) -> DictionaryVector {
         unsafe { duckdb_slice_vector(self.ptr, dict_len, selection_vector.as_ptr(), selection_vector.len()) }
         DictionaryVector::from(self.ptr)
@@ -173,6 +177,23 @@ impl FlatVector {
         std::mem::forget(dict_id);
     }
 
+    /// Make the vector a dictionary vector whose row `i` is the row `selection[i]` of `dictionary`.
+    ///
+    /// The values of the dictionary are shared instead of copied, so a low-cardinality column is only materialized
+    /// once per distinct value. The dictionary is usually created with
+    /// [`FlatVector::allocate_new_vector_with_capacity`] and must have the type of the vector.
+    pub fn set_dictionary(&mut self, dictionary: &FlatVector, selection: &[u32]) -> DictionaryVector {
+        assert!(
+            selection.iter().all(|&idx| (idx as usize) < dictionary.capacity()),
+            "selection out of range of the dictionary"
+        );
+        self.reference(dictionary);
+        self.slice(dictionary.capacity() as u64, SelectionVector::new_copy(selection))
+    }
+
+    /// Make every row of the vector the given value.
+    ///
+    /// The value is stored once, whatever the number of rows of the output.
     pub fn assign_to_constant(&mut self, value: &Value) {
         // Copies value internally
         unsafe { duckdb_vector_reference_value(self.ptr, value.ptr) }
@@ -282,6 +303,20 @@ impl ListVector {
         ListVector::from(unsafe { duckdb_list_vector_get_child(self.entries.ptr) })
     }
 
+    /// Take the child as [MapVector], with room for `capacity` maps.
+    pub fn map_child(&self, capacity: usize) -> MapVector {
+        self.reserve(capacity);
+        MapVector::from(ListVector {
+            entries: FlatVector::with_capacity(unsafe { duckdb_list_vector_get_child(self.entries.ptr) }, capacity),
+        })
+    }
+
+    /// Take the child as [UnionVector].
+    pub fn union_child(&self, capacity: usize) -> UnionVector {
+        self.reserve(capacity);
+        UnionVector::from(unsafe { duckdb_list_vector_get_child(self.entries.ptr) })
+    }
+
     /// Set primitive data to the child node.
     pub fn set_child<T: Copy>(&self, data: &[T]) {
         self.child(data.len()).copy(data);
@@ -398,6 +433,16 @@ impl StructVector {
         ArrayVector::from(unsafe { duckdb_struct_vector_get_child(self.ptr, idx as u64) })
     }
 
+    /// Take the child as [MapVector].
+    pub fn map_vector_child(&self, idx: usize) -> MapVector {
+        MapVector::from(unsafe { duckdb_struct_vector_get_child(self.ptr, idx as u64) })
+    }
+
+    /// Take the child as [UnionVector].
+    pub fn union_vector_child(&self, idx: usize) -> UnionVector {
+        UnionVector::from(unsafe { duckdb_struct_vector_get_child(self.ptr, idx as u64) })
+    }
+
     /// Get the logical type of this struct vector.
     pub fn logical_type(&self) -> LogicalTypeHandle {
         unsafe { LogicalTypeHandle::new(duckdb_vector_get_column_type(self.ptr)) }
@@ -428,6 +473,190 @@ impl StructVector {
     }
 }
 
+/// A map vector.
+///
+/// A map is stored as a list of `key`/`value` structs, see [ListVector].
+pub struct MapVector {
+    list: ListVector,
+}
+
+impl From<duckdb_vector> for MapVector {
+    fn from(ptr: duckdb_vector) -> Self {
+        Self {
+            list: ListVector::from(ptr),
+        }
+    }
+}
+
+/// A `MAP` is stored as a `LIST` of key/value `STRUCT`s.
+impl From<ListVector> for MapVector {
+    fn from(list: ListVector) -> Self {
+        Self { list }
+    }
+}
+
+impl MapVector {
+    /// Returns the number of key/value pairs in the map vector.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns true if the map vector has no key/value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns the key/value pairs as a [StructVector] with the `key` and `value` children.
+    pub fn entries(&self, capacity: usize) -> StructVector {
+        self.list.struct_child(capacity)
+    }
+
+    /// Returns the keys of the key/value pairs.
+    pub fn keys(&self, capacity: usize) -> FlatVector {
+        self.entries(capacity).child(0, capacity)
+    }
+
+    /// Returns the values of the key/value pairs.
+    pub fn values(&self, capacity: usize) -> FlatVector {
+        self.entries(capacity).child(1, capacity)
+    }
+
+    /// Set offset and length of the key/value pairs of the entry.
+    pub fn set_entry(&mut self, idx: usize, offset: usize, length: usize) {
+        self.list.set_entry(idx, offset, length);
+    }
+
+    /// Set row as null
+    pub fn set_null(&mut self, row: usize) {
+        self.list.set_null(row);
+    }
+
+    /// Set the number of key/value pairs of the map vector.
+    pub fn set_len(&self, new_len: usize) {
+        self.list.set_len(new_len);
+    }
+
+    /// Append `length` key/value pairs as the entry of `row` and return the offset of the first pair.
+    ///
+    /// The pairs are then written at `offset..offset + length` of [MapVector::keys] and [MapVector::values].
+    pub fn append_entry(&mut self, row: usize, length: usize) -> usize {
+        let offset = self.len();
+        self.list.reserve(offset + length);
+        self.set_entry(row, offset, length);
+        self.set_len(offset + length);
+        offset
+    }
+
+    /// Append primitive keys and values as the entry of `row`.
+    pub fn append_pairs<K: Copy, V: Copy>(&mut self, row: usize, keys: &[K], values: &[V]) {
+        assert_eq!(keys.len(), values.len(), "every key needs a value");
+        let offset = self.append_entry(row, keys.len());
+        let end = offset + keys.len();
+        self.keys(end).as_mut_slice_with_len::<K>(end)[offset..].copy_from_slice(keys);
+        self.values(end).as_mut_slice_with_len::<V>(end)[offset..].copy_from_slice(values);
+    }
+}
+
+/// A union vector.
+///
+/// A union is stored as a struct whose first child holds the tag of every row and the other children hold the
+/// members. The tag is the index of the member of a row, the other members must be null in that row.
+pub struct UnionVector {
+    ptr: duckdb_vector,
+}
+
+impl From<duckdb_vector> for UnionVector {
+    fn from(ptr: duckdb_vector) -> Self {
+        Self { ptr }
+    }
+}
+
+/// A `UNION` is stored as a `STRUCT` of its tags and members.
+impl From<StructVector> for UnionVector {
+    fn from(vector: StructVector) -> Self {
+        Self { ptr: vector.ptr }
+    }
+}
+
+impl UnionVector {
+    /// Returns the tags, a `UTINYINT` vector.
+    pub fn tags(&self, capacity: usize) -> FlatVector {
+        FlatVector::with_capacity(unsafe { duckdb_struct_vector_get_child(self.ptr, 0) }, capacity)
+    }
+
+    /// Set the member of `row` to the member at `idx` and the other members of the row to null.
+    ///
+    /// The value itself is then written to the same row of the [UnionVector::member].
+    pub fn set_tag(&mut self, row: usize, idx: usize) {
+        let num_members = self.num_members();
+        assert!(idx < num_members, "union member {idx} out of range");
+        self.tags(row + 1).as_mut_slice_with_len::<u8>(row + 1)[row] = idx as u8;
+        for other in (0..num_members).filter(|other| *other != idx) {
+            self.member(other, row + 1).set_null(row);
+        }
+    }
+
+    /// Returns the member by idx.
+    pub fn member(&self, idx: usize, capacity: usize) -> FlatVector {
+        FlatVector::with_capacity(self.member_ptr(idx), capacity)
+    }
+
+    /// Take the member as [StructVector].
+    pub fn struct_vector_member(&self, idx: usize) -> StructVector {
+        StructVector::from(self.member_ptr(idx))
+    }
+
+    /// Take the member as [ListVector].
+    pub fn list_vector_member(&self, idx: usize) -> ListVector {
+        ListVector::from(self.member_ptr(idx))
+    }
+
+    /// Take the member as [ArrayVector].
+    pub fn array_vector_member(&self, idx: usize) -> ArrayVector {
+        ArrayVector::from(self.member_ptr(idx))
+    }
+
+    /// Take the member as [MapVector].
+    pub fn map_vector_member(&self, idx: usize) -> MapVector {
+        MapVector::from(self.member_ptr(idx))
+    }
+
+    fn member_ptr(&self, idx: usize) -> duckdb_vector {
+        assert!(idx < self.num_members(), "union member {idx} out of range");
+        unsafe { duckdb_struct_vector_get_child(self.ptr, idx as u64 + 1) }
+    }
+
+    /// Get the logical type of this union vector.
+    pub fn logical_type(&self) -> LogicalTypeHandle {
+        unsafe { LogicalTypeHandle::new(duckdb_vector_get_column_type(self.ptr)) }
+    }
+
+    /// Get the name of the member by idx.
+    pub fn member_name(&self, idx: usize) -> DuckDbString {
+        let logical_type = self.logical_type();
+        unsafe { DuckDbString::from_ptr(duckdb_union_type_member_name(logical_type.ptr, idx as u64)) }
+    }
+
+    /// Get the number of members.
+    pub fn num_members(&self) -> usize {
+        let logical_type = self.logical_type();
+        unsafe { duckdb_union_type_member_count(logical_type.ptr) as usize }
+    }
+
+    /// Set row as null, along with its tag and members
+    pub fn set_null(&mut self, row: usize) {
+        unsafe {
+            duckdb_vector_ensure_validity_writable(self.ptr);
+            let idx = duckdb_vector_get_validity(self.ptr);
+            duckdb_validity_set_row_invalid(idx, row as u64);
+        }
+        self.tags(row + 1).set_null(row);
+        for idx in 0..self.num_members() {
+            self.member(idx, row + 1).set_null(row);
+        }
+    }
+}
+
 pub struct DictionaryVector {
     ptr: duckdb_vector,
 }
@@ -442,4 +671,11 @@ impl DictionaryVector {
     pub fn logical_type(&self) -> LogicalTypeHandle {
         unsafe { LogicalTypeHandle::new(duckdb_vector_get_column_type(self.ptr)) }
     }
+
+    /// Set the id of the dictionary, dictionaries with the same id are assumed to hold the same values.
+    ///
+    /// This lets DuckDB reuse work done on the dictionary of one chunk, e.g. hashing, for the following chunks.
+    pub fn set_dictionary_id(&mut self, dict_id: &str) {
+        unsafe { duckdb_set_dictionary_vector_id(self.ptr, dict_id.as_ptr().cast(), dict_id.len() as u32) }
+    }
 }
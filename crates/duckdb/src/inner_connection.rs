@@ -3,6 +3,7 @@ use std::{
     mem,
     os::raw::c_char,
     ptr, str,
+    sync::Arc,
 };
 
 use super::{ffi, Appender, Config, Connection, Result};
@@ -12,15 +13,29 @@ use crate::{
     statement::Statement,
 };
 
+/// A database opened by this crate, closed once the connections and results that use it are dropped.
+pub(crate) struct RawDatabase(ffi::duckdb_database);
+
+// The C API may be used from any thread.
+unsafe impl Send for RawDatabase {}
+unsafe impl Sync for RawDatabase {}
+
+impl Drop for RawDatabase {
+    fn drop(&mut self) {
+        unsafe { ffi::duckdb_close(&mut self.0) };
+    }
+}
+
 pub struct InnerConnection {
     pub db: ffi::duckdb_database,
     pub con: ffi::duckdb_connection,
-    owned: bool,
+    /// The database if it was opened by this crate, `None` for a database owned by the caller.
+    database: Option<Arc<RawDatabase>>,
 }
 
 impl InnerConnection {
     #[inline]
-    pub unsafe fn new(db: ffi::duckdb_database, owned: bool) -> Result<InnerConnection> {
+    pub(crate) unsafe fn new(db: ffi::duckdb_database, database: Option<Arc<RawDatabase>>) -> Result<InnerConnection> {
         let mut con: ffi::duckdb_connection = ptr::null_mut();
         let r = ffi::duckdb_connect(db, &mut con);
         if r != ffi::DuckDBSuccess {
@@ -30,7 +45,7 @@ impl InnerConnection {
                 Some("connect error".to_owned()),
             ));
         }
-        Ok(InnerConnection { db, con, owned })
+        Ok(InnerConnection { db, con, database })
     }
 
     pub fn open_with_flags(c_path: &CStr, config: Config) -> Result<InnerConnection> {
@@ -43,7 +58,7 @@ impl InnerConnection {
                 ffi::duckdb_free(c_err as *mut c_void);
                 return Err(Error::DuckDBFailure(ffi::Error::new(r), msg));
             }
-            InnerConnection::new(db, true)
+            InnerConnection::new(db, Some(Arc::new(RawDatabase(db))))
         }
     }
 
@@ -58,8 +73,8 @@ impl InnerConnection {
             ffi::duckdb_disconnect(&mut self.con);
             self.con = ptr::null_mut();
 
-            if self.owned {
-                ffi::duckdb_close(&mut self.db);
+            // the database is closed when its last connection or result is dropped
+            if self.database.take().is_some() {
                 self.db = ptr::null_mut();
             }
         }
@@ -68,7 +83,12 @@ impl InnerConnection {
 
     /// Creates a new connection to the already-opened database.
     pub fn try_clone(&self) -> Result<Self> {
-        unsafe { InnerConnection::new(self.db, false) }
+        unsafe { InnerConnection::new(self.db, self.database.clone()) }
+    }
+
+    /// The database if it was opened by this crate, results that outlive the connection keep it open.
+    pub(crate) fn database(&self) -> Option<Arc<RawDatabase>> {
+        self.database.clone()
     }
 
     pub fn execute(&mut self, sql: &str) -> Result<()> {
@@ -57,6 +57,10 @@
 
 pub use libduckdb_sys as ffi;
 
+// lets derive macros refer to `::duckdb` in this crate's own tests
+#[cfg(test)]
+extern crate self as duckdb;
+
 use std::{
     cell::RefCell,
     convert,
@@ -73,18 +77,25 @@ pub use crate::r2d2::DuckdbConnectionManager;
 pub use crate::{
     appender::Appender,
     appender_params::{appender_params_from_iter, AppenderParams, AppenderParamsFromIter},
-    arrow_batch::{Arrow, ArrowStream},
+    arrow_batch::{Arrow, ArrowReader, ArrowStream},
+    arrow_options::ArrowOptions,
     cache::CachedStatement,
     column::Column,
     config::{AccessMode, Config, DefaultNullOrder, DefaultOrder},
     error::Error,
     ffi::ErrorCode,
-    params::{params_from_iter, Params, ParamsFromIter},
-    row::{AndThenRows, Map, MappedRows, Row, RowIndex, Rows},
+    params::{params_from_iter, Params, ParamsFromIter, ToRow},
+    row::{AndThenRows, FromRow, Map, MappedRows, Row, RowIndex, Rows},
     statement::Statement,
     transaction::{DropBehavior, Savepoint, Transaction, TransactionBehavior},
     types::ToSql,
 };
+/// Derive [`FromRow`](trait@FromRow) for a struct.
+#[cfg(feature = "derive")]
+pub use duckdb_loadable_macros::FromRow;
+/// Derive [`ToRow`](trait@ToRow) for a struct.
+#[cfg(feature = "derive")]
+pub use duckdb_loadable_macros::ToRow;
 #[cfg(feature = "polars")]
 pub use polars_dataframe::Polars;
 
@@ -103,6 +114,7 @@ mod error;
 mod appender;
 mod appender_params;
 mod arrow_batch;
+mod arrow_options;
 mod cache;
 mod column;
 mod config;
@@ -115,6 +127,8 @@ mod pragma;
 mod r2d2;
 mod raw_statement;
 mod row;
+#[cfg(feature = "serde")]
+pub mod serde;
 mod statement;
 mod transaction;
 
@@ -227,6 +241,8 @@ pub struct Connection {
     db: RefCell<InnerConnection>,
     cache: StatementCache,
     path: Option<PathBuf>,
+    #[cfg(feature = "vtab-arrow")]
+    arrow_tables: vtab::ArrowTables,
 }
 
 unsafe impl Send for Connection {}
@@ -277,10 +293,12 @@ impl Connection {
     /// Need to pass in a valid db instance
     #[inline]
     pub unsafe fn open_from_raw(raw: ffi::duckdb_database) -> Result<Connection> {
-        InnerConnection::new(raw, false).map(|db| Connection {
+        InnerConnection::new(raw, None).map(|db| Connection {
             db: RefCell::new(db),
             cache: StatementCache::with_capacity(STATEMENT_CACHE_DEFAULT_CAPACITY),
             path: None, // Can we know the path from connection?
+            #[cfg(feature = "vtab-arrow")]
+            arrow_tables: Default::default(),
         })
     }
 
@@ -310,6 +328,8 @@ impl Connection {
             db: RefCell::new(db),
             cache: StatementCache::with_capacity(STATEMENT_CACHE_DEFAULT_CAPACITY),
             path: Some(path.as_ref().to_path_buf()),
+            #[cfg(feature = "vtab-arrow")]
+            arrow_tables: Default::default(),
         })
     }
 
@@ -561,6 +581,8 @@ impl Connection {
             db: RefCell::new(inner),
             cache: StatementCache::with_capacity(STATEMENT_CACHE_DEFAULT_CAPACITY),
             path: self.path.clone(),
+            #[cfg(feature = "vtab-arrow")]
+            arrow_tables: Default::default(),
         })
     }
 
@@ -177,6 +177,24 @@ impl_for_array_ref!(
     18 19 20 21 22 23 24 25 26 27 29 30 31 32
 );
 
+/// A type whose fields are the values of a row, in column order.
+///
+/// A reference to a `ToRow` can be passed as [`Params`] to a statement and as
+/// [`AppenderParams`](crate::AppenderParams) to an appender. It can be derived
+/// for structs with the `derive` feature and `#[derive(ToRow)]`.
+pub trait ToRow {
+    /// The values of the row.
+    fn to_row(&self) -> Vec<&dyn ToSql>;
+}
+
+impl<T: ToRow + ?Sized> Sealed for &T {}
+impl<T: ToRow + ?Sized> Params for &T {
+    #[inline]
+    fn __bind_in(self, stmt: &mut Statement<'_>) -> Result<()> {
+        stmt.bind_parameters(self.to_row())
+    }
+}
+
 /// Adapter type which allows any iterator over [`ToSql`] values to implement
 /// [`Params`].
 ///
@@ -98,11 +98,21 @@ impl DuckdbConnectionManager {
     #[cfg(feature = "vscalar")]
     pub fn register_scalar_function<S: VScalar>(&self, name: &str) -> Result<()>
     where
-        S::State: Debug,
+        S::State: Debug + Default,
     {
         let conn = self.connection.lock().unwrap();
         conn.register_scalar_function::<S>(name)
     }
+
+    /// Register a scalar function with the given state.
+    #[cfg(feature = "vscalar")]
+    pub fn register_scalar_function_with_state<S: VScalar>(&self, name: &str, state: S::State) -> Result<()>
+    where
+        S::State: Debug,
+    {
+        let conn = self.connection.lock().unwrap();
+        conn.register_scalar_function_with_state::<S>(name, state)
+    }
 }
 
 impl r2d2::ManageConnection for DuckdbConnectionManager {
@@ -81,35 +81,7 @@ impl RawStatement {
 
     #[inline]
     pub fn step(&self) -> Option<StructArray> {
-        let out = self.result?;
-        unsafe {
-            let mut arrays = FFI_ArrowArray::empty();
-            if ffi::duckdb_query_arrow_array(
-                out,
-                &mut std::ptr::addr_of_mut!(arrays) as *mut _ as *mut ffi::duckdb_arrow_array,
-            )
-            .ne(&ffi::DuckDBSuccess)
-            {
-                return None;
-            }
-
-            if arrays.is_empty() {
-                return None;
-            }
-
-            let mut schema = FFI_ArrowSchema::empty();
-            if ffi::duckdb_query_arrow_schema(
-                out,
-                &mut std::ptr::addr_of_mut!(schema) as *mut _ as *mut ffi::duckdb_arrow_schema,
-            ) != ffi::DuckDBSuccess
-            {
-                return None;
-            }
-
-            let array_data = from_ffi(arrays, &schema).expect("ok");
-            let struct_array = StructArray::from(array_data);
-            Some(struct_array)
-        }
+        unsafe { arrow_result_step(self.result?) }
     }
 
     #[inline]
@@ -293,6 +265,12 @@ impl RawStatement {
         }
     }
 
+    /// Take the Arrow result out of the statement, the caller becomes responsible for destroying it.
+    pub fn take_result(&mut self) -> Option<(ffi::duckdb_arrow, SchemaRef)> {
+        let result = self.result.take()?;
+        Some((result, self.schema()))
+    }
+
     #[inline]
     pub fn reset_result(&mut self) {
         self.schema = None;
@@ -329,3 +307,36 @@ impl Drop for RawStatement {
         }
     }
 }
+
+/// Fetch the next chunk of an Arrow query result, `None` once the result is exhausted.
+///
+/// # Safety
+/// `out` must be a valid, not yet destroyed, Arrow query result.
+pub(crate) unsafe fn arrow_result_step(out: ffi::duckdb_arrow) -> Option<StructArray> {
+    let mut arrays = FFI_ArrowArray::empty();
+    if ffi::duckdb_query_arrow_array(
+        out,
+        &mut std::ptr::addr_of_mut!(arrays) as *mut _ as *mut ffi::duckdb_arrow_array,
+    )
+    .ne(&ffi::DuckDBSuccess)
+    {
+        return None;
+    }
+
+    if arrays.is_empty() {
+        return None;
+    }
+
+    let mut schema = FFI_ArrowSchema::empty();
+    if ffi::duckdb_query_arrow_schema(
+        out,
+        &mut std::ptr::addr_of_mut!(schema) as *mut _ as *mut ffi::duckdb_arrow_schema,
+    ) != ffi::DuckDBSuccess
+    {
+        return None;
+    }
+
+    let array_data = from_ffi(arrays, &schema).expect("ok");
+    let struct_array = StructArray::from(array_data);
+    Some(struct_array)
+}
@@ -1,7 +1,7 @@
 use std::{convert, sync::Arc};
 
 use super::{Error, Result, Statement};
-use crate::types::{self, EnumType, FromSql, FromSqlError, ListType, ValueRef};
+use crate::types::{self, Decimal128, EnumType, FromSql, FromSqlError, ListType, ValueRef};
 
 use arrow::{
     array::{self, Array, ArrayRef, DictionaryArray, FixedSizeListArray, ListArray, MapArray, StructArray},
@@ -9,7 +9,6 @@ use arrow::{
 };
 use fallible_iterator::FallibleIterator;
 use fallible_streaming_iterator::FallibleStreamingIterator;
-use rust_decimal::prelude::*;
 
 /// An handle for the resulting rows of a query.
 #[must_use = "Rows is lazy and will do nothing unless consumed"]
@@ -307,6 +306,9 @@ impl<'stmt> Row<'stmt> {
             }
             FromSqlError::OutOfRange(i) => Error::IntegralValueOutOfRange(idx, i),
             FromSqlError::Other(err) => Error::FromSqlConversionFailure(idx, value.data_type(), err),
+            err @ FromSqlError::InvalidElement(..) => {
+                Error::FromSqlConversionFailure(idx, value.data_type(), Box::new(err))
+            }
             #[cfg(feature = "uuid")]
             FromSqlError::InvalidUuidSize(_) => {
                 Error::InvalidColumnType(idx, self.stmt.column_name_unwrap(idx).into(), value.data_type())
@@ -488,7 +490,11 @@ impl<'stmt> Row<'stmt> {
                 if array.scale() == 0 {
                     return ValueRef::HugeInt(array.value(row));
                 }
-                ValueRef::Decimal(Decimal::from_i128_with_scale(array.value(row), array.scale() as u32))
+                ValueRef::Decimal(Decimal128::new(
+                    array.value(row),
+                    array.precision(),
+                    array.scale() as u8,
+                ))
             }
             DataType::Timestamp(unit, _) if *unit == TimeUnit::Second => {
                 let array = column.as_any().downcast_ref::<array::TimestampSecondArray>().unwrap();
@@ -686,8 +692,24 @@ impl RowIndex for &'_ str {
     }
 }
 
+/// A type that can be read from a [`Row`], see [`Statement::query_as`](crate::Statement::query_as).
+///
+/// It is implemented for tuples, which read the columns in order, and can be derived for structs with the `derive`
+/// feature and `#[derive(FromRow)]`.
+pub trait FromRow: Sized {
+    /// Read the value from the row.
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
 macro_rules! tuple_try_from_row {
     ($($field:ident),*) => {
+        impl<$($field,)*> FromRow for ($($field,)*) where $($field: FromSql,)* {
+            #[inline]
+            fn from_row(row: &Row<'_>) -> Result<Self> {
+                Self::try_from(row)
+            }
+        }
+
         impl<'a, $($field,)*> convert::TryFrom<&'a Row<'a>> for ($($field,)*) where $($field: FromSql,)* {
             type Error = crate::Error;
 
@@ -843,4 +865,107 @@ mod tests {
         // We don't test one bigger because it's unimplemented
         Ok(())
     }
+
+    #[test]
+    fn test_query_as_tuple() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare("SELECT i, i::VARCHAR FROM range(3) t(i)")?;
+        let rows = stmt.query_as::<(i64, String), _>([])?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(rows[2], (2, "2".to_owned()));
+        assert_eq!(stmt.query_row_as::<(i64,), _>([])?, (0,));
+        Ok(())
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_from_row() -> Result<()> {
+        use crate::FromRow;
+
+        #[derive(Debug, PartialEq, FromRow)]
+        struct Address {
+            city: String,
+            zip: Option<String>,
+        }
+
+        #[derive(Debug, PartialEq, FromRow)]
+        struct Person {
+            #[duckdb(rename = "full_name")]
+            name: String,
+            age: i32,
+            #[duckdb(default)]
+            nickname: String,
+            #[duckdb(flatten)]
+            address: Address,
+        }
+
+        #[derive(Debug, PartialEq, FromRow)]
+        struct Pair(i32, #[duckdb(default)] Option<i32>);
+
+        let conn = Connection::open_in_memory()?;
+        let person = conn
+            .prepare("SELECT 'Ada' AS full_name, 36 AS age, 'London' AS city, NULL AS zip")?
+            .query_row_as::<Person, _>([])?;
+        assert_eq!(
+            person,
+            Person {
+                name: "Ada".to_owned(),
+                age: 36,
+                nickname: String::new(),
+                address: Address {
+                    city: "London".to_owned(),
+                    zip: None,
+                },
+            }
+        );
+
+        let mut stmt = conn.prepare("SELECT 1")?;
+        assert_eq!(stmt.query_row_as::<Pair, _>([])?, Pair(1, None));
+        let mut stmt = conn.prepare("SELECT 'one'")?;
+        assert!(stmt.query_row_as::<Pair, _>([]).is_err());
+        let mut stmt = conn.prepare("SELECT 'Ada' AS full_name")?;
+        assert!(matches!(
+            stmt.query_row_as::<Person, _>([]),
+            Err(crate::Error::InvalidColumnName(name)) if name == "age"
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_to_row() -> Result<()> {
+        use crate::{FromRow, ToRow};
+
+        #[derive(Debug, PartialEq, FromRow, ToRow)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Debug, PartialEq, FromRow, ToRow)]
+        struct Label {
+            text: String,
+            #[duckdb(flatten)]
+            at: Point,
+        }
+
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch("CREATE TABLE labels (text VARCHAR, x INTEGER, y INTEGER)")?;
+        let first = Label {
+            text: "origin".to_owned(),
+            at: Point { x: 0, y: 0 },
+        };
+        conn.execute("INSERT INTO labels VALUES (?, ?, ?)", &first)?;
+        let second = Label {
+            text: "corner".to_owned(),
+            at: Point { x: 3, y: 4 },
+        };
+        conn.appender("labels")?.append_row(&second)?;
+
+        let labels = conn
+            .prepare("SELECT * FROM labels ORDER BY x")?
+            .query_as::<Label, _>([])?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(labels, [first, second]);
+        Ok(())
+    }
 }
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use serde::{
+    de::{
+        self,
+        value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+        DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+
+use super::Error;
+use crate::{core, ffi, types::Value, Row};
+
+/// Deserialize a result row into `T`.
+///
+/// A struct reads its fields from the columns of the same name, and a tuple reads the columns in order.
+///
+/// ## Failure
+///
+/// Returns an `Error::InvalidColumnName` if a field has no column, and an `Error::FromSqlConversionFailure` if the
+/// value of a column cannot be deserialized into the type of its field.
+pub fn from_row<T: DeserializeOwned>(row: &Row<'_>) -> crate::Result<T> {
+    T::deserialize(RowDeserializer { row }).map_err(|err| err.0)
+}
+
+/// The error of deserializing a row, errors of columns carry the column.
+#[derive(Debug)]
+struct RowError(crate::Error);
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for RowError {}
+
+impl de::Error for RowError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        RowError(crate::Error::DuckDBFailure(
+            ffi::Error::new(ffi::DuckDBError),
+            Some(msg.to_string()),
+        ))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        RowError(crate::Error::InvalidColumnName(field.to_owned()))
+    }
+}
+
+struct RowDeserializer<'a, 'stmt> {
+    row: &'a Row<'stmt>,
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'_, '_> {
+    type Error = RowError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_map(Columns::new(self.row))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RowError> {
+        visitor.visit_seq(Columns::new(self.row))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, RowError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, RowError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RowError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        map struct enum identifier ignored_any
+    }
+}
+
+/// The columns of a row, as a map from their names to their values or as a sequence of values.
+struct Columns<'a, 'stmt> {
+    row: &'a Row<'stmt>,
+    names: Vec<String>,
+    idx: usize,
+}
+
+impl<'a, 'stmt> Columns<'a, 'stmt> {
+    fn new(row: &'a Row<'stmt>) -> Self {
+        let names = row.as_ref().column_names();
+        Columns { row, names, idx: 0 }
+    }
+
+    fn next_value<'de, T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, RowError> {
+        let idx = self.idx;
+        self.idx += 1;
+        let value = self.row.get_ref(idx).map_err(RowError)?;
+        let data_type = value.data_type();
+        seed.deserialize(ValueDeserializer(value.to_owned()))
+            .map_err(|err| RowError(crate::Error::FromSqlConversionFailure(idx, data_type, Box::new(err))))
+    }
+}
+
+impl<'de> MapAccess<'de> for Columns<'_, '_> {
+    type Error = RowError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, RowError> {
+        match self.names.get(self.idx) {
+            Some(name) => seed.deserialize(name.as_str().into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, RowError> {
+        self.next_value(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len() - self.idx)
+    }
+}
+
+impl<'de> SeqAccess<'de> for Columns<'_, '_> {
+    type Error = RowError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, RowError> {
+        if self.idx == self.names.len() {
+            return Ok(None);
+        }
+        self.next_value(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len() - self.idx)
+    }
+}
+
+/// Deserializes an owned value.
+struct ValueDeserializer(Value);
+
+impl ValueDeserializer {
+    fn visit_seq<'de, V: Visitor<'de>>(values: Vec<Value>, visitor: V) -> Result<V::Value, Error> {
+        let mut seq = SeqDeserializer::new(values.into_iter().map(ValueDeserializer));
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn visit_map<'de, K, V>(entries: impl Iterator<Item = (K, Value)>, visitor: V) -> Result<V::Value, Error>
+    where
+        K: IntoDeserializer<'de, Error>,
+        V: Visitor<'de>,
+    {
+        let mut map = MapDeserializer::new(entries.map(|(key, value)| (key, ValueDeserializer(value))));
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Boolean(value) => visitor.visit_bool(value),
+            Value::TinyInt(value) => visitor.visit_i8(value),
+            Value::SmallInt(value) => visitor.visit_i16(value),
+            Value::Int(value) => visitor.visit_i32(value),
+            Value::BigInt(value) => visitor.visit_i64(value),
+            Value::HugeInt(value) => visitor.visit_i128(value),
+            Value::UTinyInt(value) => visitor.visit_u8(value),
+            Value::USmallInt(value) => visitor.visit_u16(value),
+            Value::UInt(value) => visitor.visit_u32(value),
+            Value::UBigInt(value) => visitor.visit_u64(value),
+            Value::Float(value) => visitor.visit_f32(value),
+            Value::Double(value) => visitor.visit_f64(value),
+            Value::Decimal(value) => visitor.visit_string(value.to_string()),
+            Value::Text(value) | Value::Enum(value) => visitor.visit_string(value),
+            Value::Blob(value) => visitor.visit_byte_buf(value),
+            ref value @ (Value::Timestamp(..) | Value::Date32(_) | Value::Time64(..) | Value::Interval { .. }) => {
+                let value = core::Value::try_from(value).map_err(de::Error::custom)?;
+                visitor.visit_string(value.to_varchar())
+            }
+            Value::List(values) | Value::Array(values) => Self::visit_seq(values, visitor),
+            Value::Struct(fields) => Self::visit_map(fields.into_iter(), visitor),
+            Value::Map(entries) => Self::visit_map(
+                entries.into_iter().map(|(key, value)| (ValueDeserializer(key), value)),
+                visitor,
+            ),
+            Value::Union(value) => ValueDeserializer(*value).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Decimal(value) => visitor.visit_f64(value.to_f64()),
+            Value::Union(value) => ValueDeserializer(*value).deserialize_f64(visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Text(variant) | Value::Enum(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Struct(fields) if fields.iter().count() == 1 => {
+                let fields = fields.into_iter().map(|(key, value)| (key, ValueDeserializer(value)));
+                visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(fields)))
+            }
+            Value::Map(entries) if entries.iter().count() == 1 => {
+                let entries = entries
+                    .into_iter()
+                    .map(|(key, value)| (ValueDeserializer(key), ValueDeserializer(value)));
+                visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(entries)))
+            }
+            Value::Union(value) => ValueDeserializer(*value).deserialize_enum(name, variants, visitor),
+            value => Err(de::Error::custom(format!(
+                "invalid value {value:?}, expected a variant of enum {name}"
+            ))),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
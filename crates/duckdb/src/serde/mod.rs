@@ -0,0 +1,244 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! [`from_row`] deserializes a result row into any [`Deserialize`](::serde::Deserialize) type and
+//! [`Appender::append_serialize`](crate::Appender::append_serialize) appends any
+//! [`Serialize`](::serde::Serialize) type as a row.
+//!
+//! A row is a map from column names to values, or a sequence of values for tuples. Values map to serde as
+//! follows:
+//!
+//! * `NULL` is `None` or `()`,
+//! * numbers, strings and blobs are the corresponding serde primitives, a `DECIMAL` is a string when the Rust type
+//!   is not a float,
+//! * dates, times, timestamps and intervals are their DuckDB text representation,
+//! * a `LIST` or `ARRAY` is a sequence,
+//! * a `STRUCT` is a struct or a map with string keys,
+//! * a `MAP` is a map,
+//! * an `ENUM` is a string, which also deserializes into a unit variant,
+//! * a `UNION` is its current member.
+//!
+//! Rust enums with data are a `STRUCT` with a single field named after the variant.
+//!
+//! ```rust,no_run
+//! # use duckdb::{Connection, Result};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Person {
+//!     name: String,
+//!     tags: Vec<String>,
+//! }
+//!
+//! fn copy_people(conn: &Connection) -> Result<()> {
+//!     let mut stmt = conn.prepare("SELECT name, tags FROM people")?;
+//!     let people = stmt
+//!         .query_map([], duckdb::serde::from_row::<Person>)?
+//!         .collect::<Result<Vec<_>>>()?;
+//!     let mut app = conn.appender("people_copy")?;
+//!     for person in &people {
+//!         app.append_serialize(person)?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+
+pub use self::de::from_row;
+pub(crate) use self::ser::to_row;
+
+mod de;
+mod ser;
+
+/// Error converting a value from or to serde.
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::from_row;
+    use crate::{types::Type, Connection, Error, Result};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Shape {
+        Circle(f64),
+        Square { side: f64 },
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Everything {
+        id: i64,
+        list: Vec<i32>,
+        point: Point,
+        map: HashMap<String, i32>,
+        color: Color,
+        missing: Option<i32>,
+        price: f64,
+        exact_price: String,
+        day: String,
+        circle: Shape,
+        square: Shape,
+    }
+
+    #[test]
+    fn test_from_row() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TYPE color AS ENUM ('Red', 'Green')")?;
+        let sql = "SELECT 1 AS id, [1, 2] AS list, {'x': 1, 'y': 'a'} AS point, MAP {'k': 2} AS map,
+                   'Green'::color AS color, NULL::INT AS missing, 1.5::DECIMAL(4, 1) AS price,
+                   12.25::DECIMAL(4, 2) AS exact_price, DATE '2021-01-02' AS day, {'Circle': 2.0} AS circle,
+                   {'Square': {'side': 3.0}} AS square";
+        let value: Everything = db.query_row(sql, [], from_row)?;
+        assert_eq!(
+            value,
+            Everything {
+                id: 1,
+                list: vec![1, 2],
+                point: Point {
+                    x: 1,
+                    y: "a".to_owned()
+                },
+                map: HashMap::from([("k".to_owned(), 2)]),
+                color: Color::Green,
+                missing: None,
+                price: 1.5,
+                exact_price: "12.25".to_owned(),
+                day: "2021-01-02".to_owned(),
+                circle: Shape::Circle(2.0),
+                square: Shape::Square { side: 3.0 },
+            }
+        );
+
+        let tuple: (i32, String) = db.query_row("SELECT 1, 'a'", [], from_row)?;
+        assert_eq!(tuple, (1, "a".to_owned()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_row_errors() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let missing = db.query_row("SELECT 1 AS x", [], from_row::<Point>);
+        assert_eq!(missing.unwrap_err(), Error::InvalidColumnName("y".to_owned()));
+
+        let invalid = db.query_row("SELECT 1 AS x, [1] AS y", [], from_row::<Point>);
+        match invalid.unwrap_err() {
+            Error::FromSqlConversionFailure(1, Type::List(_), err) => {
+                assert!(err.to_string().contains("expected a string"), "{err}")
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Row {
+        id: i32,
+        tags: Vec<String>,
+        point: Point,
+        color: Color,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_append_serialize() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TYPE color AS ENUM ('Red', 'Green');
+             CREATE TABLE t (id INT, tags VARCHAR[], point STRUCT(x INT, y VARCHAR), color color, note VARCHAR)",
+        )?;
+        let rows = [
+            Row {
+                id: 1,
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                point: Point {
+                    x: 1,
+                    y: "a".to_owned(),
+                },
+                color: Color::Red,
+                note: None,
+            },
+            Row {
+                id: 2,
+                tags: vec![],
+                point: Point {
+                    x: 2,
+                    y: "b".to_owned(),
+                },
+                color: Color::Green,
+                note: Some("note".to_owned()),
+            },
+        ];
+        {
+            let mut app = db.appender("t")?;
+            for row in &rows {
+                app.append_serialize(row)?;
+            }
+            app.append_serialize(&(
+                3,
+                ["c"],
+                Point {
+                    x: 3,
+                    y: "c".to_owned(),
+                },
+                Color::Red,
+                "tuple",
+            ))?;
+        }
+
+        let mut stmt = db.prepare("SELECT * FROM t ORDER BY id")?;
+        let read = stmt.query_map([], from_row::<Row>)?.collect::<Result<Vec<_>>>()?;
+        assert_eq!(read[..2], rows);
+        assert_eq!(read[2].tags, ["c"]);
+        assert_eq!(read[2].note.as_deref(), Some("tuple"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_serialize_errors() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (m MAP(VARCHAR, INT))")?;
+        let mut app = db.appender("t")?;
+        assert!(matches!(
+            app.append_serialize(&1),
+            Err(Error::ToSqlConversionFailure(_))
+        ));
+        let map = HashMap::from([("k".to_owned(), 1)]);
+        assert!(app.append_serialize(&(map,)).is_err());
+        Ok(())
+    }
+}
@@ -2,15 +2,21 @@ use std::{convert, ffi::c_void, fmt, mem, os::raw::c_char, ptr, str};
 
 use arrow::{array::StructArray, datatypes::SchemaRef};
 
-use super::{ffi, AndThenRows, Connection, Error, MappedRows, Params, RawStatement, Result, Row, Rows, ValueRef};
+use super::{
+    ffi, AndThenRows, Connection, Error, FromRow, MappedRows, Params, RawStatement, Result, Row, Rows, ValueRef,
+};
 #[cfg(feature = "polars")]
 use crate::{arrow2, polars_dataframe::Polars};
 use crate::{
-    arrow_batch::{Arrow, ArrowStream},
+    arrow_batch::{Arrow, ArrowReader, ArrowStream},
+    core,
     error::result_from_duckdb_prepare,
-    types::{TimeUnit, ToSql, ToSqlOutput},
+    types::{TimeUnit, ToSql, ToSqlOutput, Value},
 };
 
+/// The function reading the rows of [`Statement::query_as`].
+type FromRowFn<T> = fn(&Row<'_>) -> Result<T>;
+
 /// A prepared statement.
 pub struct Statement<'conn> {
     conn: &'conn Connection,
@@ -109,6 +115,39 @@ impl Statement<'_> {
         Ok(Arrow::new(self))
     }
 
+    /// Execute the prepared statement, returning an owned reader of the
+    /// resulting arrow RecordBatches
+    ///
+    /// The reader does not borrow the statement and can be converted into an
+    /// [`FFI_ArrowArrayStream`](arrow::ffi_stream::FFI_ArrowArrayStream). It keeps the database open, so it may
+    /// outlive the connection.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{ArrowReader, Result, Connection};
+    /// # use arrow::ffi_stream::FFI_ArrowArrayStream;
+    /// fn export_arrow_stream(conn: &Connection) -> Result<FFI_ArrowArrayStream> {
+    ///     let reader: ArrowReader = conn.prepare("SELECT * FROM test")?.query_arrow_reader([])?;
+    ///     Ok(FFI_ArrowArrayStream::from(reader))
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if binding parameters fails.
+    #[inline]
+    pub fn query_arrow_reader<P: Params>(&mut self, params: P) -> Result<ArrowReader> {
+        self.execute(params)?;
+        let (result, schema) = self.stmt.take_result().ok_or_else(|| {
+            Error::DuckDBFailure(
+                ffi::Error::new(ffi::DuckDBError),
+                Some("the statement did not produce a result".to_owned()),
+            )
+        })?;
+        Ok(ArrowReader::new(result, schema, self.conn.db.borrow().database()))
+    }
+
     /// Execute the prepared statement, returning a handle to the resulting
     /// vector of arrow RecordBatch in streaming way
     ///
@@ -279,6 +318,32 @@ impl Statement<'_> {
         self.query(params).map(|rows| rows.mapped(f))
     }
 
+    /// Executes the prepared statement and reads every resulting row as a
+    /// `T`, see [`FromRow`].
+    ///
+    /// This is equivalent to `stmt.query_map(params, T::from_row)`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{Connection, Result};
+    /// fn get_people(conn: &Connection) -> Result<Vec<(String, i32)>> {
+    ///     let mut stmt = conn.prepare("SELECT name, age FROM people")?;
+    ///     stmt.query_as::<(String, i32), _>([])?.collect()
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if binding parameters fails.
+    pub fn query_as<T, P>(&mut self, params: P) -> Result<MappedRows<'_, FromRowFn<T>>>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        self.query_map(params, T::from_row as FromRowFn<T>)
+    }
+
     /// Executes the prepared statement and maps a function over the resulting
     /// rows, where the function returns a `Result` with `Error` type
     /// implementing `std::convert::From<Error>` (so errors can be unified).
@@ -349,6 +414,22 @@ impl Statement<'_> {
         self.query(params)?.get_expected_row().and_then(f)
     }
 
+    /// Convenience method to execute a query that is expected to return a
+    /// single row and read it as a `T`, see [`FromRow`].
+    ///
+    /// This is equivalent to `stmt.query_row(params, T::from_row)`.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the underlying DuckDB call fails.
+    pub fn query_row_as<T, P>(&mut self, params: P) -> Result<T>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        self.query_row(params, T::from_row)
+    }
+
     /// Return the row count
     #[inline]
     pub fn row_count(&self) -> usize {
@@ -498,6 +579,7 @@ impl Statement<'_> {
         let ptr = unsafe { self.stmt.ptr() };
         let value = match value {
             ToSqlOutput::Borrowed(v) => v,
+            ToSqlOutput::Owned(ref v) if v.is_nested() => return self.bind_nested(v, col),
             ToSqlOutput::Owned(ref v) => ValueRef::from(v),
         };
         // TODO: bind more
@@ -521,6 +603,7 @@ impl Statement<'_> {
             ValueRef::UBigInt(i) => unsafe { ffi::duckdb_bind_uint64(ptr, col as u64, i) },
             ValueRef::Float(r) => unsafe { ffi::duckdb_bind_float(ptr, col as u64, r) },
             ValueRef::Double(r) => unsafe { ffi::duckdb_bind_double(ptr, col as u64, r) },
+            ValueRef::Decimal(d) => unsafe { ffi::duckdb_bind_decimal(ptr, col as u64, d.checked()?.into()) },
             ValueRef::Text(s) => unsafe {
                 ffi::duckdb_bind_varchar_length(ptr, col as u64, s.as_ptr() as *const c_char, s.len() as u64)
             },
@@ -545,6 +628,14 @@ impl Statement<'_> {
         result_from_duckdb_prepare(rc, ptr)
     }
 
+    /// Bind a nested value as a `duckdb_value`, `MAP` and `UNION` values cannot be created and are an error.
+    fn bind_nested(&self, value: &Value, col: usize) -> Result<()> {
+        let value = core::Value::try_from(value)?;
+        let ptr = unsafe { self.stmt.ptr() };
+        let rc = unsafe { ffi::duckdb_bind_value(ptr, col as u64, value.ptr) };
+        result_from_duckdb_prepare(rc, ptr)
+    }
+
     #[inline]
     fn execute_with_bound_parameters(&mut self) -> Result<usize> {
         self.stmt.execute()
@@ -967,4 +1058,45 @@ mod test {
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_query_arrow_reader() -> Result<()> {
+        use arrow::{
+            array::Int64Array,
+            ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream},
+            record_batch::RecordBatchReader,
+        };
+
+        let db = Connection::open_in_memory()?;
+        let reader = {
+            let mut stmt = db.prepare("SELECT range AS i FROM range(?)")?;
+            stmt.query_arrow_reader([5000])?
+        };
+        assert_eq!(reader.schema().field(0).name(), "i");
+
+        // the reader outlives the statement and can be exported through the C stream interface
+        let stream = ArrowArrayStreamReader::try_new(FFI_ArrowArrayStream::from(reader)).unwrap();
+        let mut sum = 0;
+        for batch in stream {
+            let batch = batch.unwrap();
+            let column = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+            sum += column.values().iter().sum::<i64>();
+        }
+        assert_eq!(sum, (0..5000).sum::<i64>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_arrow_reader_outlives_connection() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        // large enough to spill out of the first buffers of the result
+        let reader = db
+            .prepare("SELECT range AS i FROM range(2000000)")?
+            .query_arrow_reader([])?;
+        drop(db);
+
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 2_000_000);
+        Ok(())
+    }
 }
@@ -1,8 +1,7 @@
 use pretty_assertions::assert_eq;
-use rust_decimal::Decimal;
 
 use crate::{
-    types::{OrderedMap, TimeUnit, Type, Value, ValueRef},
+    types::{Decimal128, OrderedMap, TimeUnit, Type, Value, ValueRef},
     Connection,
 };
 
@@ -20,9 +19,9 @@ fn test_large_arrow_types() -> crate::Result<()> {
 }
 
 fn test_with_database(database: &Connection) -> crate::Result<()> {
-    // uhugeint, time_tz, and dec38_10 aren't supported in the duckdb arrow layer
+    // uhugeint and time_tz aren't supported in the duckdb arrow layer
     // union is currently blocked by https://github.com/duckdb/duckdb/pull/11326
-    let excluded = ["uhugeint", "time_tz", "dec38_10", "union", "varint"];
+    let excluded = ["uhugeint", "time_tz", "union", "varint"];
 
     let mut binding = database.prepare(&format!(
         "SELECT * EXCLUDE ({}) FROM test_all_types()",
@@ -117,8 +116,8 @@ fn test_single(idx: &mut i32, column: String, value: ValueRef) {
             _ => assert_eq!(value, ValueRef::Null),
         },
         "decimal" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(0, 0))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(1, 0))),
+            0 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(0, 38, 0))),
+            1 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(1, 38, 0))),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "date" => match idx {
@@ -157,23 +156,28 @@ fn test_single(idx: &mut i32, column: String, value: ValueRef) {
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_4_1" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(-9999, 1))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(9999, 1))),
+            0 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(-9999, 4, 1))),
+            1 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(9999, 4, 1))),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_9_4" => match idx {
-            0 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(-999999999, 4))),
-            1 => assert_eq!(value, ValueRef::Decimal(Decimal::from_i128_with_scale(999999999, 4))),
+            0 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(-999999999, 9, 4))),
+            1 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(999999999, 9, 4))),
             _ => assert_eq!(value, ValueRef::Null),
         },
         "dec_18_6" => match idx {
+            0 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(-999999999999999999, 18, 6))),
+            1 => assert_eq!(value, ValueRef::Decimal(Decimal128::new(999999999999999999, 18, 6))),
+            _ => assert_eq!(value, ValueRef::Null),
+        },
+        "dec38_10" => match idx {
             0 => assert_eq!(
                 value,
-                ValueRef::Decimal(Decimal::from_i128_with_scale(-999999999999999999, 6))
+                ValueRef::Decimal(Decimal128::new(-99999999999999999999999999999999999999, 38, 10))
             ),
             1 => assert_eq!(
                 value,
-                ValueRef::Decimal(Decimal::from_i128_with_scale(999999999999999999, 6))
+                ValueRef::Decimal(Decimal128::new(99999999999999999999999999999999999999, 38, 10))
             ),
             _ => assert_eq!(value, ValueRef::Null),
         },
@@ -0,0 +1,408 @@
+//! [`Decimal128`], the value of a DuckDB `DECIMAL` of up to 38 digits, and its conversions.
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    ffi,
+    types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef},
+    Error, Result,
+};
+
+/// A `DECIMAL(width, scale)` value, stored as its unscaled `value` with `scale` digits after the decimal point.
+///
+/// `DECIMAL` values of any width are read into a `Decimal128` without loss, and a `Decimal128` binds or appends
+/// as a `DECIMAL` of its own width and scale. Binding or appending a decimal that is not
+/// [valid](Decimal128::is_valid) fails.
+///
+/// Decimals compare and hash by their numeric value, so `1.50` as a `DECIMAL(4, 2)` equals `1.5` as a
+/// `DECIMAL(2, 1)`. Compare the fields to tell them apart.
+///
+/// ```rust
+/// # use duckdb::types::Decimal128;
+/// let price = Decimal128::new(12345, 10, 2);
+/// assert_eq!(price.to_string(), "123.45");
+/// assert_eq!(price, Decimal128::new(123450, 6, 3));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Decimal128 {
+    /// The unscaled value, i.e. the decimal times `10^scale`.
+    pub value: i128,
+    /// The total number of digits, at most [`Decimal128::MAX_WIDTH`].
+    pub width: u8,
+    /// The number of digits after the decimal point, at most `width`.
+    pub scale: u8,
+}
+
+impl Decimal128 {
+    /// The largest width of a DuckDB `DECIMAL`.
+    pub const MAX_WIDTH: u8 = 38;
+
+    /// Create a decimal from its unscaled value, width and scale.
+    #[inline]
+    pub fn new(value: i128, width: u8, scale: u8) -> Decimal128 {
+        Decimal128 { value, width, scale }
+    }
+
+    /// The closest `f64` to the decimal.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale.into())
+    }
+
+    /// Whether DuckDB can hold the decimal, i.e. its width is between 1 and [`Decimal128::MAX_WIDTH`], its scale is
+    /// at most its width and its value has at most `width` digits.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        (1..=Self::MAX_WIDTH).contains(&self.width)
+            && self.scale <= self.width
+            && self.value.unsigned_abs() < 10u128.pow(self.width.into())
+    }
+
+    /// The decimal, or an error if it is not valid.
+    pub(crate) fn checked(self) -> Result<Self> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(Error::ToSqlConversionFailure(
+                format!(
+                    "{} does not fit in a DECIMAL({}, {})",
+                    self.value, self.width, self.scale
+                )
+                .into(),
+            ))
+        }
+    }
+
+    /// The unscaled value and scale without trailing zeros after the decimal point.
+    fn normalized(&self) -> (i128, u8) {
+        let (mut value, mut scale) = (self.value, self.scale);
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        (value, scale)
+    }
+}
+
+impl PartialEq for Decimal128 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for Decimal128 {}
+
+impl Hash for Decimal128 {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+impl fmt::Display for Decimal128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = usize::from(self.scale);
+        let digits = self.value.unsigned_abs().to_string();
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        let sign = if self.value < 0 { "-" } else { "" };
+        if scale == 0 {
+            f.pad(&format!("{sign}{int}"))
+        } else {
+            f.pad(&format!("{sign}{int}.{frac}"))
+        }
+    }
+}
+
+impl From<Decimal128> for ffi::duckdb_decimal {
+    #[inline]
+    fn from(decimal: Decimal128) -> Self {
+        ffi::duckdb_decimal {
+            width: decimal.width,
+            scale: decimal.scale,
+            value: ffi::duckdb_hugeint {
+                lower: decimal.value as u64,
+                upper: (decimal.value >> 64) as i64,
+            },
+        }
+    }
+}
+
+impl From<ffi::duckdb_decimal> for Decimal128 {
+    #[inline]
+    fn from(decimal: ffi::duckdb_decimal) -> Self {
+        let value = ((decimal.value.upper as i128) << 64) | decimal.value.lower as i128;
+        Decimal128::new(value, decimal.width, decimal.scale)
+    }
+}
+
+impl ToSql for Decimal128 {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Decimal(*self)))
+    }
+}
+
+/// Read a `DECIMAL`, or an integer as a decimal of scale 0.
+impl FromSql for Decimal128 {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Decimal(decimal) => Ok(decimal),
+            ValueRef::TinyInt(i) => Ok(Decimal128::new(i.into(), 3, 0)),
+            ValueRef::SmallInt(i) => Ok(Decimal128::new(i.into(), 5, 0)),
+            ValueRef::Int(i) => Ok(Decimal128::new(i.into(), 10, 0)),
+            ValueRef::BigInt(i) => Ok(Decimal128::new(i.into(), 19, 0)),
+            ValueRef::HugeInt(i) => Ok(Decimal128::new(i, Decimal128::MAX_WIDTH, 0)),
+            ValueRef::UTinyInt(i) => Ok(Decimal128::new(i.into(), 3, 0)),
+            ValueRef::USmallInt(i) => Ok(Decimal128::new(i.into(), 5, 0)),
+            ValueRef::UInt(i) => Ok(Decimal128::new(i.into(), 10, 0)),
+            ValueRef::UBigInt(i) => Ok(Decimal128::new(i.into(), 20, 0)),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal {
+    use rust_decimal::Decimal;
+
+    use super::Decimal128;
+    use crate::{
+        types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef},
+        Result,
+    };
+
+    /// A `rust_decimal::Decimal` has at most 29 digits, so it is a `DECIMAL(38, scale)`.
+    impl From<Decimal> for Decimal128 {
+        #[inline]
+        fn from(decimal: Decimal) -> Self {
+            Decimal128::new(decimal.mantissa(), Decimal128::MAX_WIDTH, decimal.scale() as u8)
+        }
+    }
+
+    /// Fails if the decimal has more than 28 digits after the decimal point or does not fit in 96 bits.
+    impl TryFrom<Decimal128> for Decimal {
+        type Error = rust_decimal::Error;
+
+        #[inline]
+        fn try_from(decimal: Decimal128) -> Result<Self, Self::Error> {
+            Decimal::try_from_i128_with_scale(decimal.value, decimal.scale.into())
+        }
+    }
+
+    impl ToSql for Decimal {
+        #[inline]
+        fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::Owned(Value::Decimal(Decimal128::from(*self))))
+        }
+    }
+
+    impl FromSql for Decimal {
+        #[inline]
+        fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+            let decimal = Decimal128::column_result(value)?;
+            Decimal::try_from(decimal).map_err(|err| FromSqlError::Other(Box::new(err)))
+        }
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal {
+    use bigdecimal::{num_bigint::BigInt, BigDecimal};
+
+    use super::Decimal128;
+    use crate::{
+        types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef},
+        Error, Result,
+    };
+
+    impl From<Decimal128> for BigDecimal {
+        #[inline]
+        fn from(decimal: Decimal128) -> Self {
+            BigDecimal::new(BigInt::from(decimal.value), decimal.scale.into())
+        }
+    }
+
+    /// A `DECIMAL(38, scale)`, fails if the decimal needs more than 38 digits.
+    impl TryFrom<&BigDecimal> for Decimal128 {
+        type Error = Error;
+
+        fn try_from(decimal: &BigDecimal) -> Result<Self> {
+            let out_of_range = || Error::ToSqlConversionFailure(format!("{decimal} does not fit in a DECIMAL").into());
+            let decimal = match decimal.as_bigint_and_exponent().1 {
+                scale if scale < 0 => decimal.with_scale(0),
+                scale if scale > Decimal128::MAX_WIDTH.into() => decimal.normalized(),
+                _ => decimal.clone(),
+            };
+            let (value, scale) = decimal.into_bigint_and_exponent();
+            let value = i128::try_from(&value).map_err(|_| out_of_range())?;
+            let scale = u8::try_from(scale).map_err(|_| out_of_range())?;
+            Decimal128::new(value, Decimal128::MAX_WIDTH, scale)
+                .checked()
+                .map_err(|_| out_of_range())
+        }
+    }
+
+    impl ToSql for BigDecimal {
+        #[inline]
+        fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::Owned(Value::Decimal(Decimal128::try_from(self)?)))
+        }
+    }
+
+    impl FromSql for BigDecimal {
+        #[inline]
+        fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+            Decimal128::column_result(value).map(BigDecimal::from)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Decimal128;
+    use crate::{Connection, Error, Result};
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Decimal128::new(12345, 5, 2).to_string(), "123.45");
+        assert_eq!(Decimal128::new(-5, 3, 3).to_string(), "-0.005");
+        assert_eq!(Decimal128::new(-42, 2, 0).to_string(), "-42");
+        assert_eq!(Decimal128::new(0, 4, 1).to_string(), "0.0");
+        assert_eq!(
+            Decimal128::new(i128::MIN, 38, 10).to_string(),
+            "-17014118346046923173168730371.5884105728"
+        );
+        assert_eq!(format!("{:>6}", Decimal128::new(15, 2, 1)), "   1.5");
+        assert_eq!(Decimal128::new(-125, 3, 2).to_f64(), -1.25);
+    }
+
+    #[test]
+    fn test_wide_decimals() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (d DECIMAL(38, 10))")?;
+        let max = Decimal128::new(99_999_999_999_999_999_999_999_999_999_999_999_999, 38, 10);
+        db.execute("INSERT INTO t VALUES (?)", [max])?;
+        {
+            let mut app = db.appender("t")?;
+            app.append_row([Decimal128::new(-max.value, 38, 10)])?;
+            app.append_row([Decimal128::new(15, 3, 1)])?;
+        }
+
+        let mut stmt = db.prepare("SELECT d, d::VARCHAR FROM t ORDER BY d")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, Decimal128>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(rows[0].0, Decimal128::new(-max.value, 38, 10));
+        assert_eq!((rows[1].0.value, rows[1].0.scale), (15_000_000_000, 10));
+        assert_eq!(rows[2].0, max);
+        for (decimal, text) in &rows {
+            assert_eq!(&decimal.to_string(), text);
+        }
+
+        let small: Decimal128 =
+            db.query_row("SELECT ?::DECIMAL(4, 1)", [Decimal128::new(-12345, 6, 3)], |r| r.get(0))?;
+        assert_eq!((small.value, small.width, small.scale), (-123, 4, 1));
+        let int: Decimal128 = db.query_row("SELECT 7::INTEGER", [], |r| r.get(0))?;
+        assert_eq!((int.value, int.width, int.scale), (7, 10, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_decimals() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (d DECIMAL(38, 10))")?;
+        let invalid = [
+            Decimal128::new(1, 0, 0),
+            Decimal128::new(1, 39, 0),
+            Decimal128::new(1, 2, 3),
+            Decimal128::new(100, 2, 0),
+            Decimal128::new(-100, 2, 1),
+            Decimal128::new(i128::MAX, 38, 0),
+        ];
+        for decimal in invalid {
+            assert!(!decimal.is_valid(), "{decimal:?}");
+            let err = db.query_row("SELECT ?", [decimal], |r| r.get::<_, Decimal128>(0));
+            assert!(matches!(err, Err(Error::ToSqlConversionFailure(_))), "{err:?}");
+            let err = db.query_row("SELECT ?", [vec![decimal]], |r| r.get::<_, Vec<Decimal128>>(0));
+            assert!(err.is_err(), "{err:?}");
+            let mut app = db.appender("t")?;
+            assert!(matches!(
+                app.append_row([decimal]),
+                Err(Error::ToSqlConversionFailure(_))
+            ));
+        }
+        assert!(Decimal128::new(-99, 2, 2).is_valid());
+        let count: i64 = db.query_row("SELECT count(*) FROM t", [], |r| r.get(0))?;
+        assert_eq!(count, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_equality() {
+        use std::collections::HashSet;
+
+        assert_eq!(Decimal128::new(150, 4, 2), Decimal128::new(15, 2, 1));
+        assert_eq!(Decimal128::new(-3000, 10, 3), Decimal128::new(-3, 1, 0));
+        assert_eq!(Decimal128::new(0, 5, 4), Decimal128::new(0, 1, 0));
+        assert_ne!(Decimal128::new(15, 3, 1), Decimal128::new(15, 3, 2));
+        assert_ne!(Decimal128::new(150, 4, 2), Decimal128::new(-15, 2, 1));
+
+        let set: HashSet<_> = [Decimal128::new(150, 4, 2), Decimal128::new(15, 2, 1)].into();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_bigdecimal() -> Result<()> {
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+
+        let db = Connection::open_in_memory()?;
+        let decimal = BigDecimal::from_str("-1234567890123456789012345.6789").unwrap();
+        let read: BigDecimal = db.query_row("SELECT ?", [&decimal], |r| r.get(0))?;
+        assert_eq!(read, decimal);
+        let read: BigDecimal = db.query_row("SELECT 12.50::DECIMAL(4, 2)", [], |r| r.get(0))?;
+        assert_eq!(read, BigDecimal::from_str("12.5").unwrap());
+
+        assert_eq!(
+            Decimal128::try_from(&BigDecimal::from_str("1.2e3").unwrap())?,
+            Decimal128::new(1200, 38, 0)
+        );
+        assert_eq!(
+            Decimal128::try_from(&BigDecimal::from_str("1.5000000000000000000000000000000000000000").unwrap())?,
+            Decimal128::new(15, 38, 1)
+        );
+        for out_of_range in ["1e38", "0.000000000000000000000000000000000000001"] {
+            let decimal = BigDecimal::from_str(out_of_range).unwrap();
+            assert!(Decimal128::try_from(&decimal).is_err());
+            let err = db.query_row("SELECT ?", [&decimal], |r| r.get::<_, BigDecimal>(0));
+            assert!(matches!(err, Err(Error::ToSqlConversionFailure(_))), "{err:?}");
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_rust_decimal() -> Result<()> {
+        use crate::types::Type;
+        use rust_decimal::Decimal;
+
+        let db = Connection::open_in_memory()?;
+        let decimal = Decimal::new(-12345, 3);
+        let read: Decimal = db.query_row("SELECT ?", [decimal], |r| r.get(0))?;
+        assert_eq!(read, decimal);
+        assert_eq!(Decimal128::from(decimal), Decimal128::new(-12345, 38, 3));
+
+        let wide = db.query_row("SELECT 1e30::DECIMAL(38, 0) * 1000", [], |r| r.get::<_, Decimal>(0));
+        assert!(matches!(
+            wide.unwrap_err(),
+            Error::FromSqlConversionFailure(0, Type::HugeInt, _)
+        ));
+        Ok(())
+    }
+}
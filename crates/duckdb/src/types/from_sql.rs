@@ -1,7 +1,12 @@
 extern crate cast;
 
 use super::{TimeUnit, Value, ValueRef};
-use std::{error::Error, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
 
 /// Enum listing possible errors from [`FromSql`] trait.
 #[derive(Debug)]
@@ -20,6 +25,10 @@ pub enum FromSqlError {
     #[cfg(feature = "uuid")]
     InvalidUuidSize(usize),
 
+    /// Error when an element of a `LIST`, `ARRAY`, `MAP` or `STRUCT` cannot be
+    /// converted. The associated `usize` is the position of the element.
+    InvalidElement(usize, Box<FromSqlError>),
+
     /// An error case available for implementors of the [`FromSql`] trait.
     Other(Box<dyn Error + Send + Sync + 'static>),
 }
@@ -31,6 +40,7 @@ impl PartialEq for FromSqlError {
             (FromSqlError::OutOfRange(n1), FromSqlError::OutOfRange(n2)) => n1 == n2,
             #[cfg(feature = "uuid")]
             (FromSqlError::InvalidUuidSize(s1), FromSqlError::InvalidUuidSize(s2)) => s1 == s2,
+            (FromSqlError::InvalidElement(i1, e1), FromSqlError::InvalidElement(i2, e2)) => i1 == i2 && e1 == e2,
             (..) => false,
         }
     }
@@ -45,6 +55,7 @@ impl fmt::Display for FromSqlError {
             FromSqlError::InvalidUuidSize(s) => {
                 write!(f, "Cannot read UUID value out of {s} byte blob")
             }
+            FromSqlError::InvalidElement(i, ref err) => write!(f, "Invalid element {i}: {err}"),
             FromSqlError::Other(ref err) => err.fmt(f),
         }
     }
@@ -52,10 +63,10 @@ impl fmt::Display for FromSqlError {
 
 impl Error for FromSqlError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        if let FromSqlError::Other(ref err) = self {
-            Some(&**err)
-        } else {
-            None
+        match self {
+            FromSqlError::InvalidElement(_, ref err) => Some(&**err),
+            FromSqlError::Other(ref err) => Some(&**err),
+            _ => None,
         }
     }
 }
@@ -67,11 +78,21 @@ pub type FromSqlResult<T> = Result<T, FromSqlError>;
 pub trait FromSql: Sized {
     /// Converts DuckDB value into Rust value.
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self>;
+
+    /// Converts the bytes of a `BLOB` or `VARCHAR` into a `Vec`, which only
+    /// `u8` supports so that `Vec<u8>` reads blobs.
+    #[doc(hidden)]
+    #[inline]
+    fn vec_from_bytes(_bytes: &[u8]) -> FromSqlResult<Vec<Self>> {
+        Err(FromSqlError::InvalidType)
+    }
 }
 
 macro_rules! from_sql_integral(
-    ($t:ident) => (
+    ($t:ident $($extra:tt)*) => (
         impl FromSql for $t {
+            $($extra)*
+
             #[inline]
             fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
                 match value {
@@ -169,7 +190,12 @@ from_sql_integral!(i32);
 from_sql_integral!(i64);
 from_sql_integral!(i128);
 from_sql_integral!(isize);
-from_sql_integral!(u8);
+from_sql_integral!(u8
+    #[inline]
+    fn vec_from_bytes(bytes: &[u8]) -> FromSqlResult<Vec<Self>> {
+        Ok(bytes.to_vec())
+    }
+);
 from_sql_integral!(u16);
 from_sql_integral!(u32);
 from_sql_integral!(u64);
@@ -225,13 +251,106 @@ impl FromSql for std::sync::Arc<str> {
     }
 }
 
-impl FromSql for Vec<u8> {
+/// Convert the element at `index` of a nested value.
+#[inline]
+fn element<T: FromSql>(index: usize, value: ValueRef<'_>) -> FromSqlResult<T> {
+    T::column_result(value).map_err(|err| FromSqlError::InvalidElement(index, Box::new(err)))
+}
+
+/// Reads a `LIST` or `ARRAY`, and for `Vec<u8>` the bytes of a `BLOB`.
+impl<T: FromSql> FromSql for Vec<T> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(bytes) | ValueRef::Text(bytes) => T::vec_from_bytes(bytes),
+            _ => value
+                .elements()?
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| element(index, value))
+                .collect(),
+        }
+    }
+}
+
+/// Reads an `ARRAY` or a `LIST` of exactly `N` elements.
+impl<T: FromSql, const N: usize> FromSql for [T; N] {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Vec::<T>::column_result(value)?.try_into().map_err(|values: Vec<T>| {
+            FromSqlError::Other(format!("Expected {N} elements, found {}", values.len()).into())
+        })
+    }
+}
+
+impl<K, V, S> FromSql for HashMap<K, V, S>
+where
+    K: FromSql + Eq + Hash,
+    V: FromSql,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .entries()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| Ok((element(index, key)?, element(index, value)?)))
+            .collect()
+    }
+}
+
+impl<K: FromSql + Ord, V: FromSql> FromSql for BTreeMap<K, V> {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-        value.as_blob().map(|b| b.to_vec())
+        value
+            .entries()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| Ok((element(index, key)?, element(index, value)?)))
+            .collect()
     }
 }
 
+macro_rules! tuple_from_sql {
+    ($($field:ident),*) => {
+        /// Reads a `STRUCT` with as many fields as the tuple, by position.
+        impl<$($field,)*> FromSql for ($($field,)*) where $($field: FromSql,)* {
+            // we end with index += 1, which rustc warns about
+            #[allow(unused_assignments)]
+            #[inline]
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                let fields = value.fields()?;
+                let expected = [$(stringify!($field)),*].len();
+                if fields.len() != expected {
+                    return Err(FromSqlError::Other(
+                        format!("Expected {expected} fields, found {}", fields.len()).into(),
+                    ));
+                }
+                let mut index = 0;
+                $(
+                    #[allow(non_snake_case)]
+                    let $field = element::<$field>(index, fields[index])?;
+                    index += 1;
+                )*
+                Ok(($($field,)*))
+            }
+        }
+    }
+}
+
+macro_rules! tuples_from_sql {
+    ($first:ident) => {
+        tuple_from_sql!($first);
+    };
+    ($first:ident $(, $remaining:ident)*) => {
+        tuple_from_sql!($first $(, $remaining)*);
+        tuples_from_sql!($($remaining),*);
+    };
+}
+
+tuples_from_sql!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
 #[cfg(feature = "uuid")]
 impl FromSql for uuid::Uuid {
     #[inline]
@@ -417,4 +536,76 @@ mod test {
         assert_eq!(v.0.to_string(), "47183823-2574-4bfd-b411-99ed177d3e43");
         Ok(())
     }
+
+    #[test]
+    fn test_collections() -> Result<()> {
+        use std::collections::{BTreeMap, HashMap};
+
+        let db = Connection::open_in_memory()?;
+        let list: Vec<i32> = db.query_row("SELECT [1, 2, 3]", [], |row| row.get(0))?;
+        assert_eq!(list, [1, 2, 3]);
+        let nested: Vec<Vec<Option<String>>> = db.query_row("SELECT [['a', NULL], []]", [], |row| row.get(0))?;
+        assert_eq!(nested, [vec![Some("a".to_owned()), None], vec![]]);
+        let blob: Vec<u8> = db.query_row("SELECT '\\x2A\\x2B'::BLOB", [], |row| row.get(0))?;
+        assert_eq!(blob, [0x2A, 0x2B]);
+
+        let array: [f64; 3] = db.query_row("SELECT [1.0, 2.0, 3.0]::DOUBLE[3]", [], |row| row.get(0))?;
+        assert_eq!(array, [1.0, 2.0, 3.0]);
+        let from_list: [i64; 2] = db.query_row("SELECT [1, 2]", [], |row| row.get(0))?;
+        assert_eq!(from_list, [1, 2]);
+
+        let map: HashMap<String, i64> = db.query_row("SELECT MAP {'a': 1, 'b': 2}", [], |row| row.get(0))?;
+        assert_eq!(map, HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]));
+        let ordered: BTreeMap<i32, Vec<i32>> = db.query_row("SELECT MAP {2: [], 1: [1]}", [], |row| row.get(0))?;
+        assert_eq!(ordered, BTreeMap::from([(1, vec![1]), (2, vec![])]));
+
+        let tuple: (i32, String, Option<bool>) =
+            db.query_row("SELECT {'a': 1, 'b': 'x', 'c': NULL::BOOLEAN}", [], |row| row.get(0))?;
+        assert_eq!(tuple, (1, "x".to_owned(), None));
+        let points: Vec<(i32, i32)> =
+            db.query_row("SELECT [{'x': 1, 'y': 2}, {'x': 3, 'y': 4}]", [], |row| row.get(0))?;
+        assert_eq!(points, [(1, 2), (3, 4)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_errors() -> Result<()> {
+        use super::FromSqlError;
+
+        let db = Connection::open_in_memory()?;
+        let err = db
+            .query_row("SELECT [[1], [2, NULL]]", [], |row| row.get::<_, Vec<Vec<i32>>>(0))
+            .unwrap_err();
+        match err {
+            Error::FromSqlConversionFailure(0, _, err) => {
+                let err = err.downcast::<FromSqlError>().unwrap();
+                let expected = FromSqlError::InvalidElement(
+                    1,
+                    Box::new(FromSqlError::InvalidElement(1, Box::new(FromSqlError::InvalidType))),
+                );
+                assert_eq!(*err, expected);
+                assert_eq!(err.to_string(), "Invalid element 1: Invalid element 1: Invalid type");
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+
+        let err = db
+            .query_row("SELECT [1, 2]", [], |row| row.get::<_, [i32; 3]>(0))
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected 3 elements, found 2"), "{err}");
+        let err = db
+            .query_row("SELECT {'a': 1}", [], |row| row.get::<_, (i32, i32)>(0))
+            .unwrap_err();
+        assert!(err.to_string().contains("Expected 2 fields, found 1"), "{err}");
+        let err = db
+            .query_row("SELECT 1", [], |row| row.get::<_, Vec<i32>>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+        // only bytes are read from a BLOB or VARCHAR
+        for sql in ["SELECT '\\x2A'::BLOB", "SELECT 'abc'"] {
+            let err = db.query_row(sql, [], |row| row.get::<_, Vec<u16>>(0)).unwrap_err();
+            assert!(matches!(err, Error::InvalidColumnType(..)), "{err:?}");
+        }
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@
 //! a value was NULL (which gets translated to `None`).
 
 pub use self::{
+    decimal::Decimal128,
     from_sql::{FromSql, FromSqlError, FromSqlResult},
     ordered_map::OrderedMap,
     string::DuckString,
@@ -16,6 +17,7 @@ use std::fmt;
 
 #[cfg(feature = "chrono")]
 mod chrono;
+mod decimal;
 mod from_sql;
 #[cfg(feature = "serde_json")]
 mod serde_json;
@@ -222,7 +224,7 @@ mod test {
     fn test_empty_blob() -> Result<()> {
         let db = checked_memory_handle()?;
 
-        let empty = vec![];
+        let empty: Vec<u8> = vec![];
         db.execute("INSERT INTO foo(b) VALUES (?)", [&empty])?;
 
         let v: Vec<u8> = db.query_row("SELECT b FROM foo", [], |r| r.get(0))?;
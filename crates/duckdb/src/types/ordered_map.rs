@@ -26,3 +26,12 @@ impl<K: std::cmp::PartialEq, V> OrderedMap<K, V> {
         self.0.iter()
     }
 }
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
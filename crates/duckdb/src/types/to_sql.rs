@@ -1,6 +1,9 @@
-use super::{Null, TimeUnit, Value, ValueRef};
+use super::{Null, OrderedMap, TimeUnit, Value, ValueRef};
 use crate::Result;
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+};
 
 /// `ToSqlOutput` represents the possible output types for implementers of the
 /// [`ToSql`] trait.
@@ -65,7 +68,7 @@ impl ToSql for ToSqlOutput<'_> {
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
         Ok(match *self {
             ToSqlOutput::Borrowed(v) => ToSqlOutput::Borrowed(v),
-            ToSqlOutput::Owned(ref v) => ToSqlOutput::Borrowed(ValueRef::from(v)),
+            ToSqlOutput::Owned(ref v) => v.to_sql()?,
         })
     }
 }
@@ -75,6 +78,25 @@ impl ToSql for ToSqlOutput<'_> {
 pub trait ToSql {
     /// Converts Rust value to DuckDB value
     fn to_sql(&self) -> Result<ToSqlOutput<'_>>;
+
+    /// Converts a slice of values, which is a `LIST` except for `u8` whose
+    /// slices are a `BLOB`.
+    #[doc(hidden)]
+    fn slice_to_sql(values: &[Self]) -> Result<ToSqlOutput<'_>>
+    where
+        Self: Sized,
+    {
+        let values = values.iter().map(to_value).collect::<Result<_>>()?;
+        Ok(ToSqlOutput::Owned(Value::List(values)))
+    }
+}
+
+/// Convert `value` into an owned value, e.g. an element of a nested value.
+fn to_value<T: ?Sized + ToSql>(value: &T) -> Result<Value> {
+    Ok(match value.to_sql()? {
+        ToSqlOutput::Borrowed(value) => value.to_owned(),
+        ToSqlOutput::Owned(value) => value,
+    })
 }
 
 impl<T: ToSql + ToOwned + ?Sized> ToSql for Cow<'_, T> {
@@ -136,7 +158,6 @@ to_sql_self!(i32);
 to_sql_self!(i64);
 to_sql_self!(i128);
 to_sql_self!(isize);
-to_sql_self!(u8);
 to_sql_self!(u16);
 to_sql_self!(u32);
 to_sql_self!(f32);
@@ -171,23 +192,116 @@ impl ToSql for str {
     }
 }
 
-impl ToSql for Vec<u8> {
+impl ToSql for u8 {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        Ok(ToSqlOutput::from(self.as_slice()))
+        Ok(ToSqlOutput::from(*self))
+    }
+
+    #[inline]
+    fn slice_to_sql(values: &[u8]) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(values))
     }
 }
 
-impl ToSql for [u8] {
+/// A `LIST`, or a `BLOB` for bytes.
+impl<T: ToSql> ToSql for [T] {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
-        Ok(ToSqlOutput::from(self))
+        T::slice_to_sql(self)
+    }
+}
+
+/// A `LIST`, or a `BLOB` for bytes.
+///
+/// Before `Vec<T>` and `[T]` were lists, `Vec<u8>` was the only vector that
+/// could be bound, so the element type of an untyped `vec![]` or `&[]`
+/// parameter was inferred as `u8`. Such a parameter now needs its type spelled
+/// out, e.g. `let empty: Vec<u8> = vec![];` for an empty `BLOB`.
+impl<T: ToSql> ToSql for Vec<T> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        self.as_slice().to_sql()
     }
 }
 
+/// A `LIST`, or a `BLOB` for bytes.
+impl<T: ToSql, const N: usize> ToSql for [T; N] {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        self.as_slice().to_sql()
+    }
+}
+
+/// The entries of a map as a `LIST` of `STRUCT(key, value)`, because the C API
+/// cannot create `MAP` values. Use `map_from_entries(?)` for a `MAP`.
+fn entries_to_sql<'a, K, V>(entries: impl Iterator<Item = (&'a K, &'a V)>) -> Result<ToSqlOutput<'static>>
+where
+    K: ToSql + 'a,
+    V: ToSql + 'a,
+{
+    let entries = entries
+        .map(|(key, value)| {
+            let entry = vec![
+                ("key".to_owned(), to_value(key)?),
+                ("value".to_owned(), to_value(value)?),
+            ];
+            Ok(Value::Struct(OrderedMap::from(entry)))
+        })
+        .collect::<Result<_>>()?;
+    Ok(ToSqlOutput::Owned(Value::List(entries)))
+}
+
+/// A `LIST` of `STRUCT(key, value)`, see `map_from_entries`.
+impl<K: ToSql, V: ToSql, S> ToSql for HashMap<K, V, S> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        entries_to_sql(self.iter())
+    }
+}
+
+/// A `LIST` of `STRUCT(key, value)`, see `map_from_entries`.
+impl<K: ToSql, V: ToSql> ToSql for BTreeMap<K, V> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        entries_to_sql(self.iter())
+    }
+}
+
+macro_rules! tuple_to_sql {
+    ($($field:ident),*) => {
+        /// An unnamed `STRUCT`, which casts to a `STRUCT` by position.
+        impl<$($field,)*> ToSql for ($($field,)*) where $($field: ToSql,)* {
+            #[inline]
+            fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+                #[allow(non_snake_case)]
+                let ($($field,)*) = self;
+                let fields = vec![$((String::new(), to_value($field)?),)*];
+                Ok(ToSqlOutput::Owned(Value::Struct(OrderedMap::from(fields))))
+            }
+        }
+    }
+}
+
+macro_rules! tuples_to_sql {
+    ($first:ident) => {
+        tuple_to_sql!($first);
+    };
+    ($first:ident $(, $remaining:ident)*) => {
+        tuple_to_sql!($first $(, $remaining)*);
+        tuples_to_sql!($($remaining),*);
+    };
+}
+
+tuples_to_sql!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
 impl ToSql for Value {
     #[inline]
     fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        // nested values have no `ValueRef` equivalent
+        if self.is_nested() {
+            return Ok(ToSqlOutput::Owned(self.clone()));
+        }
         Ok(ToSqlOutput::from(self))
     }
 }
@@ -368,4 +482,49 @@ mod test {
         assert_eq!(found_label, "target");
         Ok(())
     }
+
+    #[test]
+    fn test_nested_params() -> crate::Result<()> {
+        use crate::Connection;
+        use std::collections::{BTreeMap, HashMap};
+
+        let db = Connection::open_in_memory()?;
+        let count: i64 = db.query_row(
+            "SELECT count(*) FROM range(10) t(id) WHERE id = ANY(?)",
+            [vec![1i64, 3, 5]],
+            |row| row.get(0),
+        )?;
+        assert_eq!(count, 3);
+
+        // bytes stay a BLOB
+        let ty: String = db.query_row("SELECT typeof(?)", [vec![1u8, 2]], |row| row.get(0))?;
+        assert_eq!(ty, "BLOB");
+        let ty: String = db.query_row("SELECT typeof(?)", [vec![1u16, 2]], |row| row.get(0))?;
+        assert_eq!(ty, "USMALLINT[]");
+
+        let nested = vec![vec!["a", "b"], vec![], vec!["c"]];
+        let read: Vec<Vec<String>> = db.query_row("SELECT ?", [&nested], |row| row.get(0))?;
+        assert_eq!(read, nested);
+        let optional = [Some(1), None];
+        let read: Vec<Option<i32>> = db.query_row("SELECT ?", [optional], |row| row.get(0))?;
+        assert_eq!(read, optional);
+        let empty: Vec<i32> = vec![];
+        let len: i64 = db.query_row("SELECT len(?)", [&empty], |row| row.get(0))?;
+        assert_eq!(len, 0);
+        let array: [i32; 3] = db.query_row("SELECT ?::INTEGER[3]", [[1, 2, 3]], |row| row.get(0))?;
+        assert_eq!(array, [1, 2, 3]);
+
+        db.execute_batch("CREATE TABLE t (s STRUCT(a INTEGER, b VARCHAR))")?;
+        db.execute("INSERT INTO t VALUES (?)", [(1, "x")])?;
+        let b: String = db.query_row("SELECT s.b FROM t WHERE s = ?", [(1, "x")], |row| row.get(0))?;
+        assert_eq!(b, "x");
+
+        let map = HashMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)]);
+        let read: HashMap<String, i32> = db.query_row("SELECT map_from_entries(?)", [&map], |row| row.get(0))?;
+        assert_eq!(read, map);
+        let map = BTreeMap::from([(1, vec![1.5])]);
+        let read: BTreeMap<i32, Vec<f64>> = db.query_row("SELECT map_from_entries(?)", [&map], |row| row.get(0))?;
+        assert_eq!(read, map);
+        Ok(())
+    }
 }
@@ -1,5 +1,4 @@
-use super::{Null, OrderedMap, TimeUnit, Type};
-use rust_decimal::prelude::*;
+use super::{Decimal128, Null, OrderedMap, TimeUnit, Type};
 
 /// Owning [dynamic type value](http://sqlite.org/datatype3.html). Value's type is typically
 /// dictated by DuckDB (not by the caller).
@@ -35,7 +34,7 @@ pub enum Value {
     /// The value is a f64.
     Double(f64),
     /// The value is a Decimal.
-    Decimal(Decimal),
+    Decimal(Decimal128),
     /// The value is a timestamp.
     Timestamp(TimeUnit, i64),
     /// The value is a text string.
@@ -69,6 +68,18 @@ pub enum Value {
     Union(Box<Value>),
 }
 
+impl Value {
+    /// Whether the value is a `LIST`, `ARRAY`, `STRUCT`, `MAP` or `UNION`,
+    /// which have no [`ValueRef`](super::ValueRef) equivalent.
+    #[inline]
+    pub(crate) fn is_nested(&self) -> bool {
+        matches!(
+            self,
+            Value::List(_) | Value::Array(_) | Value::Struct(_) | Value::Map(_) | Value::Union(_)
+        )
+    }
+}
+
 impl From<Null> for Value {
     #[inline]
     fn from(_: Null) -> Value {
@@ -1,8 +1,7 @@
-use super::{Type, Value};
+use super::{Decimal128, Type, Value};
 use crate::types::{FromSqlError, FromSqlResult, OrderedMap};
 
 use crate::Row;
-use rust_decimal::prelude::*;
 
 use arrow::{
     array::{
@@ -71,7 +70,7 @@ pub enum ValueRef<'a> {
     /// The value is a f64.
     Double(f64),
     /// The value is a decimal
-    Decimal(Decimal),
+    Decimal(Decimal128),
     /// The value is a timestamp.
     Timestamp(TimeUnit, i64),
     /// The value is a text string.
@@ -189,6 +188,60 @@ impl<'a> ValueRef<'a> {
             _ => Err(FromSqlError::InvalidType),
         }
     }
+
+    /// If `self` is case `List` or `Array`, returns its elements. Otherwise,
+    /// returns [`Err(FromSqlError::InvalidType)`](FromSqlError::InvalidType).
+    pub(crate) fn elements(&self) -> FromSqlResult<Vec<ValueRef<'a>>> {
+        let (range, idx, values) = match *self {
+            ValueRef::List(ListType::Regular(items), idx) => {
+                let offsets = items.offsets();
+                (offsets[idx] as usize..offsets[idx + 1] as usize, idx, items.values())
+            }
+            ValueRef::List(ListType::Large(items), idx) => {
+                let offsets = items.offsets();
+                (offsets[idx] as usize..offsets[idx + 1] as usize, idx, items.values())
+            }
+            ValueRef::Array(items, idx) => {
+                let value_length = items.value_length() as usize;
+                (idx * value_length..(idx + 1) * value_length, idx, items.values())
+            }
+            _ => return Err(FromSqlError::InvalidType),
+        };
+        Ok(range.map(|row| Row::value_ref_internal(row, idx, values)).collect())
+    }
+
+    /// If `self` is case `Map`, returns its keys and values. Otherwise, returns
+    /// [`Err(FromSqlError::InvalidType)`](FromSqlError::InvalidType).
+    pub(crate) fn entries(&self) -> FromSqlResult<Vec<(ValueRef<'a>, ValueRef<'a>)>> {
+        match *self {
+            ValueRef::Map(arr, idx) => {
+                let (keys, values) = (arr.keys(), arr.values());
+                let offsets = arr.offsets();
+                Ok((offsets[idx] as usize..offsets[idx + 1] as usize)
+                    .map(|row| {
+                        (
+                            Row::value_ref_internal(row, idx, keys),
+                            Row::value_ref_internal(row, idx, values),
+                        )
+                    })
+                    .collect())
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+
+    /// If `self` is case `Struct`, returns the values of its fields in order.
+    /// Otherwise, returns [`Err(FromSqlError::InvalidType)`](FromSqlError::InvalidType).
+    pub(crate) fn fields(&self) -> FromSqlResult<Vec<ValueRef<'a>>> {
+        match *self {
+            ValueRef::Struct(items, idx) => Ok(items
+                .columns()
+                .iter()
+                .map(|column| Row::value_ref_internal(idx, 0, column))
+                .collect()),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
 }
 
 impl From<ValueRef<'_>> for Value {
@@ -218,26 +271,7 @@ impl From<ValueRef<'_>> for Value {
             ValueRef::Date32(d) => Value::Date32(d),
             ValueRef::Time64(t, d) => Value::Time64(t, d),
             ValueRef::Interval { months, days, nanos } => Value::Interval { months, days, nanos },
-            ValueRef::List(items, idx) => match items {
-                ListType::Regular(items) => {
-                    let offsets = items.offsets();
-                    from_list(
-                        offsets[idx].try_into().unwrap(),
-                        offsets[idx + 1].try_into().unwrap(),
-                        idx,
-                        items.values(),
-                    )
-                }
-                ListType::Large(items) => {
-                    let offsets = items.offsets();
-                    from_list(
-                        offsets[idx].try_into().unwrap(),
-                        offsets[idx + 1].try_into().unwrap(),
-                        idx,
-                        items.values(),
-                    )
-                }
-            },
+            ValueRef::List(..) => Value::List(borrowed.elements().unwrap().iter().map(ValueRef::to_owned).collect()),
             ValueRef::Enum(items, idx) => {
                 let dict_values = match items {
                     EnumType::UInt8(res) => res.values(),
@@ -266,31 +300,15 @@ impl From<ValueRef<'_>> for Value {
                     .collect();
                 Value::Struct(OrderedMap::from(value))
             }
-            ValueRef::Map(arr, idx) => {
-                let keys = arr.keys();
-                let values = arr.values();
-                let offsets = arr.offsets();
-                let range = offsets[idx]..offsets[idx + 1];
-                Value::Map(OrderedMap::from(
-                    range
-                        .map(|row| {
-                            let row = row.try_into().unwrap();
-                            let key = Row::value_ref_internal(row, idx, keys).to_owned();
-                            let value = Row::value_ref_internal(row, idx, values).to_owned();
-                            (key, value)
-                        })
-                        .collect::<Vec<_>>(),
-                ))
-            }
-            ValueRef::Array(items, idx) => {
-                let value_length = usize::try_from(items.value_length()).unwrap();
-                let range = (idx * value_length)..((idx + 1) * value_length);
-                Value::Array(
-                    range
-                        .map(|row| Row::value_ref_internal(row, idx, items.values()).to_owned())
-                        .collect(),
-                )
-            }
+            ValueRef::Map(..) => Value::Map(OrderedMap::from(
+                borrowed
+                    .entries()
+                    .unwrap()
+                    .iter()
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect::<Vec<_>>(),
+            )),
+            ValueRef::Array(..) => Value::Array(borrowed.elements().unwrap().iter().map(ValueRef::to_owned).collect()),
             ValueRef::Union(column, idx) => {
                 let column = column.as_any().downcast_ref::<UnionArray>().unwrap();
                 let type_id = column.type_id(idx);
@@ -303,14 +321,6 @@ impl From<ValueRef<'_>> for Value {
     }
 }
 
-fn from_list(start: usize, end: usize, idx: usize, values: &ArrayRef) -> Value {
-    Value::List(
-        (start..end)
-            .map(|row| Row::value_ref_internal(row, idx, values).to_owned())
-            .collect(),
-    )
-}
-
 impl<'a> From<&'a str> for ValueRef<'a> {
     #[inline]
     fn from(s: &str) -> ValueRef<'_> {
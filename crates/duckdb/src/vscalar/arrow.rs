@@ -6,8 +6,10 @@ use arrow::{
 };
 
 use crate::{
-    core::{DataChunkHandle, LogicalTypeId},
-    vtab::arrow::{data_chunk_to_arrow, write_arrow_array_to_vector, WritableVector},
+    core::{DataChunkHandle, LogicalTypeHandle},
+    vtab::arrow::{
+        check_arrow_readable, data_chunk_to_arrow, to_duckdb_logical_type, write_arrow_array_to_vector, WritableVector,
+    },
 };
 
 use super::{ScalarFunctionSignature, ScalarParams, VScalar};
@@ -29,25 +31,31 @@ impl AsRef<[DataType]> for ArrowScalarParams {
     }
 }
 
+/// The logical type of a parameter, which must also be convertible back to arrow when the function is invoked
+fn to_parameter_logical_type(data_type: &DataType) -> LogicalTypeHandle {
+    to_duckdb_logical_type(data_type)
+        .and_then(|logical_type| {
+            check_arrow_readable(&logical_type)?;
+            Ok(logical_type)
+        })
+        .unwrap_or_else(|e| panic!("parameter type {data_type} is not supported: {e}"))
+}
+
 impl From<ArrowScalarParams> for ScalarParams {
+    /// Panics if a parameter type can't be passed to an arrow scalar function, e.g. a `Union`
     fn from(params: ArrowScalarParams) -> Self {
         match params {
-            ArrowScalarParams::Exact(params) => ScalarParams::Exact(
-                params
-                    .into_iter()
-                    .map(|v| LogicalTypeId::try_from(&v).expect("type should be converted").into())
-                    .collect(),
-            ),
-            ArrowScalarParams::Variadic(param) => ScalarParams::Variadic(
-                LogicalTypeId::try_from(&param)
-                    .expect("type should be converted")
-                    .into(),
-            ),
+            ArrowScalarParams::Exact(params) => {
+                ScalarParams::Exact(params.iter().map(to_parameter_logical_type).collect())
+            }
+            ArrowScalarParams::Variadic(param) => ScalarParams::Variadic(to_parameter_logical_type(&param)),
         }
     }
 }
 
 /// A signature for a scalar function that accepts and returns arrow types
+///
+/// Registering the function panics if a parameter type can't be converted from a DuckDB vector, e.g. a `Union`.
 pub struct ArrowFunctionSignature {
     /// The parameters of the scalar function
     pub parameters: Option<ArrowScalarParams>,
@@ -77,7 +85,11 @@ impl ArrowFunctionSignature {
 pub trait VArrowScalar: Sized {
     /// State that persists across invocations of the scalar function (the lifetime of the connection)
     /// The state can be accessed by multiple threads, so it must be `Send + Sync`.
-    type State: Default + Sized + Send + Sync;
+    ///
+    /// The state is `Default::default()` unless given to [`Connection::register_scalar_function_with_state`].
+    ///
+    /// [`Connection::register_scalar_function_with_state`]: crate::Connection::register_scalar_function_with_state
+    type State: Sized + Send + Sync + 'static;
 
     /// The actual function that is called by DuckDB
     fn invoke(info: &Self::State, input: RecordBatch) -> Result<Arc<dyn Array>, Box<dyn std::error::Error>>;
@@ -85,6 +97,16 @@ pub trait VArrowScalar: Sized {
     /// The possible signatures of the scalar function. These will result in DuckDB scalar function overloads.
     /// The invoke method should be able to handle all of these signatures.
     fn signatures() -> Vec<ArrowFunctionSignature>;
+
+    /// Whether the function is volatile, see [`VScalar::volatile`].
+    fn volatile() -> bool {
+        false
+    }
+
+    /// Whether the function handles NULL inputs itself, see [`VScalar::special_null_handling`].
+    fn special_null_handling() -> bool {
+        false
+    }
 }
 
 impl<T> VScalar for T
@@ -107,12 +129,18 @@ where
             .into_iter()
             .map(|sig| ScalarFunctionSignature {
                 parameters: sig.parameters.map(Into::into),
-                return_type: LogicalTypeId::try_from(&sig.return_type)
-                    .expect("type should be converted")
-                    .into(),
+                return_type: to_duckdb_logical_type(&sig.return_type).expect("type should be converted"),
             })
             .collect()
     }
+
+    fn volatile() -> bool {
+        T::volatile()
+    }
+
+    fn special_null_handling() -> bool {
+        T::special_null_handling()
+    }
 }
 
 #[cfg(test)]
@@ -122,7 +150,7 @@ mod test {
 
     use arrow::{
         array::{Array, RecordBatch, StringArray},
-        datatypes::DataType,
+        datatypes::{DataType, Field, Fields, Float32Type, IntervalUnit, UnionFields, UnionMode},
     };
 
     use crate::{vscalar::arrow::ArrowFunctionSignature, Connection};
@@ -259,6 +287,108 @@ mod test {
         }
     }
 
+    struct ArrowScale {}
+
+    impl VArrowScalar for ArrowScale {
+        type State = f32;
+
+        fn invoke(factor: &Self::State, input: RecordBatch) -> Result<Arc<dyn Array>, Box<dyn std::error::Error>> {
+            let a = input
+                .column(0)
+                .as_any()
+                .downcast_ref::<::arrow::array::Float32Array>()
+                .unwrap();
+            Ok(Arc::new(::arrow::compute::kernels::arity::unary::<_, _, Float32Type>(
+                a,
+                |v| v * factor,
+            )))
+        }
+
+        fn signatures() -> Vec<ArrowFunctionSignature> {
+            vec![ArrowFunctionSignature::exact(
+                vec![DataType::Float32],
+                DataType::Float32,
+            )]
+        }
+    }
+
+    // returns its argument, to round trip nested types through arrow
+    struct ArrowEcho {}
+
+    impl VArrowScalar for ArrowEcho {
+        type State = ();
+
+        fn invoke(_: &Self::State, input: RecordBatch) -> Result<Arc<dyn Array>, Box<dyn std::error::Error>> {
+            Ok(input.column(0).clone())
+        }
+
+        fn signatures() -> Vec<ArrowFunctionSignature> {
+            let person = DataType::Struct(Fields::from(vec![
+                Field::new("name", DataType::Utf8, true),
+                Field::new("age", DataType::Int32, true),
+            ]));
+            let entries = Field::new(
+                "entries",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("key", DataType::Utf8, false),
+                    Field::new("value", DataType::Int64, true),
+                ])),
+                false,
+            );
+            [
+                DataType::List(Arc::new(Field::new("l", person, true))),
+                DataType::Map(Arc::new(entries), false),
+                DataType::Interval(IntervalUnit::MonthDayNano),
+            ]
+            .into_iter()
+            .map(|data_type| ArrowFunctionSignature::exact(vec![data_type.clone()], data_type))
+            .collect()
+        }
+    }
+
+    // decimal overloads are ambiguous, so every decimal type needs its own function
+    struct ArrowEchoDecimal<const P: u8, const S: i8> {}
+
+    impl<const P: u8, const S: i8> VArrowScalar for ArrowEchoDecimal<P, S> {
+        type State = ();
+
+        fn invoke(_: &Self::State, input: RecordBatch) -> Result<Arc<dyn Array>, Box<dyn std::error::Error>> {
+            Ok(input.column(0).clone())
+        }
+
+        fn signatures() -> Vec<ArrowFunctionSignature> {
+            vec![ArrowFunctionSignature::exact(
+                vec![DataType::Decimal128(P, S)],
+                DataType::Decimal128(P, S),
+            )]
+        }
+    }
+
+    // unions can be returned but not passed to an arrow scalar function
+    struct ArrowUnionParam {}
+
+    impl VArrowScalar for ArrowUnionParam {
+        type State = ();
+
+        fn invoke(_: &Self::State, input: RecordBatch) -> Result<Arc<dyn Array>, Box<dyn std::error::Error>> {
+            Ok(input.column(0).clone())
+        }
+
+        fn signatures() -> Vec<ArrowFunctionSignature> {
+            let union = DataType::Union(
+                UnionFields::new(
+                    [0, 1],
+                    [
+                        Field::new("i", DataType::Int32, true),
+                        Field::new("s", DataType::Utf8, true),
+                    ],
+                ),
+                UnionMode::Sparse,
+            );
+            vec![ArrowFunctionSignature::exact(vec![union], DataType::Int32)]
+        }
+    }
+
     #[test]
     fn test_arrow_scalar() -> Result<(), Box<dyn Error>> {
         let conn = Connection::open_in_memory()?;
@@ -333,4 +463,83 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_arrow_scalar_with_state() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_scalar_function_with_state::<ArrowScale>("double_it", 2.0)?;
+        conn.register_scalar_function_with_state::<ArrowScale>("triple_it", 3.0)?;
+
+        let (doubled, tripled): (f32, f32) =
+            conn.query_row("select double_it(1.5::FLOAT), triple_it(1.5::FLOAT)", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?;
+        assert_eq!((doubled, tripled), (3.0, 4.5));
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "is not supported")]
+    fn test_arrow_scalar_union_param() {
+        let conn = Connection::open_in_memory().unwrap();
+        let _ = conn.register_scalar_function::<ArrowUnionParam>("arrow_union_param");
+    }
+
+    #[test]
+    fn test_arrow_scalar_nested_roundtrip() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_scalar_function::<ArrowEcho>("arrow_echo")?;
+        conn.register_scalar_function::<ArrowEchoDecimal<4, 1>>("arrow_echo_d1")?;
+        conn.register_scalar_function::<ArrowEchoDecimal<10, 2>>("arrow_echo_d2")?;
+        conn.register_scalar_function::<ArrowEchoDecimal<38, 10>>("arrow_echo_d3")?;
+
+        let columns = [
+            ("arrow_echo", "l"),
+            ("arrow_echo", "m"),
+            ("arrow_echo", "iv"),
+            ("arrow_echo_d1", "d1"),
+            ("arrow_echo_d2", "d2"),
+            ("arrow_echo_d3", "d3"),
+        ];
+        let checks = columns
+            .iter()
+            .map(|(f, c)| format!("count(*) filter ({f}({c}) is not distinct from {c})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "select {checks} from (select
+                case when i % 7 = 0 then null else list_transform(range(i % 5),
+                    j -> {{'name': case when j = 2 then null else j::varchar end, 'age': j::integer}}) end as l,
+                case when i % 11 = 0 then null else map(['a', 'b'], [i, null::bigint]) end as m,
+                case when i % 17 = 0 then null else to_months((i % 13)::integer) + to_days((i % 31)::integer) + to_microseconds(i * 1001) end as iv,
+                case when i % 13 = 0 then null else (i % 1000 / 10)::decimal(4, 1) end as d1,
+                (i / 100 - 10)::decimal(10, 2) as d2,
+                (i * 1.5 - 1000)::decimal(38, 10) as d3
+            from range(3000) t(i))"
+        );
+        let counts: Vec<i64> = conn.query_row(&sql, [], |row| (0..columns.len()).map(|i| row.get(i)).collect())?;
+        assert_eq!(counts, vec![3000; columns.len()]);
+
+        let list: String = conn.query_row(
+            "select arrow_echo([{'name': 'duck', 'age': 3}, null, {'name': null, 'age': null}])::varchar",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(list, "[{'name': duck, 'age': 3}, NULL, {'name': NULL, 'age': NULL}]");
+
+        let map: String = conn.query_row("select arrow_echo(map {'x': 1, 'y': null})::varchar", [], |row| {
+            row.get(0)
+        })?;
+        assert_eq!(map, "{x=1, y=NULL}");
+
+        let decimal: String = conn.query_row(
+            "select arrow_echo_d3(-12345678901234567890.0123456789::decimal(38, 10))::varchar",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(decimal, "-12345678901234567890.0123456789");
+
+        Ok(())
+    }
 }
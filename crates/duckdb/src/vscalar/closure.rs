@@ -0,0 +1,427 @@
+use std::error::Error;
+
+use libduckdb_sys::{
+    duckdb_data_chunk, duckdb_function_info, duckdb_string_t, duckdb_string_t_data, duckdb_string_t_length,
+    duckdb_vector,
+};
+
+use super::{ScalarFunctionInfo, ScalarFunctionSetBuilder};
+use crate::{
+    core::{DataChunkHandle, FlatVector, Inserter, LogicalTypeHandle, LogicalTypeId},
+    Connection,
+};
+
+/// A Rust type that can be an argument of a closure registered with [`Connection::create_scalar_function`].
+///
+/// `Option<T>` arguments receive `None` for NULL inputs, for any other argument type the closure isn't called and
+/// the result is NULL.
+pub trait ScalarArg {
+    /// The type passed to the closure, which may borrow from the input vector.
+    type Ref<'a>;
+
+    /// Whether the argument accepts NULL.
+    const NULLABLE: bool = false;
+
+    /// The DuckDB type of the argument.
+    fn logical_type() -> LogicalTypeHandle;
+
+    /// Reads the argument of the given row.
+    ///
+    /// # Safety
+    ///
+    /// The vector must be a flat vector of [`ScalarArg::logical_type`] with at least `row + 1` rows.
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<Self::Ref<'_>, Box<dyn Error>>;
+}
+
+/// A Rust type that can be returned by a closure registered with [`Connection::create_scalar_function`].
+///
+/// `None` and `Err` results are written as NULL and as the error of the query respectively.
+pub trait ScalarReturn {
+    /// The DuckDB type of the result.
+    fn logical_type() -> LogicalTypeHandle;
+
+    /// Writes the result of the given row.
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>>;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $id:ident),* $(,)?) => {
+        $(
+            impl ScalarArg for $ty {
+                type Ref<'a> = $ty;
+
+                fn logical_type() -> LogicalTypeHandle {
+                    LogicalTypeId::$id.into()
+                }
+
+                unsafe fn read(vector: &FlatVector, row: usize) -> Result<$ty, Box<dyn Error>> {
+                    Ok(*vector.as_mut_ptr::<$ty>().add(row))
+                }
+            }
+
+            impl ScalarReturn for $ty {
+                fn logical_type() -> LogicalTypeHandle {
+                    LogicalTypeId::$id.into()
+                }
+
+                fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+                    output.as_mut_slice::<$ty>()[row] = self;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    bool => Boolean,
+    i8 => Tinyint,
+    i16 => Smallint,
+    i32 => Integer,
+    i64 => Bigint,
+    u8 => UTinyint,
+    u16 => USmallint,
+    u32 => UInteger,
+    u64 => UBigint,
+    f32 => Float,
+    f64 => Double,
+}
+
+/// The bytes of a VARCHAR or BLOB, borrowed from the vector as short strings are stored inline.
+unsafe fn read_bytes(vector: &FlatVector, row: usize) -> &[u8] {
+    let value = vector.as_mut_ptr::<duckdb_string_t>().add(row);
+    let len = duckdb_string_t_length(*value);
+    std::slice::from_raw_parts(duckdb_string_t_data(value) as *const u8, len as usize)
+}
+
+impl ScalarArg for &str {
+    type Ref<'a> = &'a str;
+
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Varchar.into()
+    }
+
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<&str, Box<dyn Error>> {
+        Ok(std::str::from_utf8(read_bytes(vector, row))?)
+    }
+}
+
+impl ScalarArg for String {
+    type Ref<'a> = String;
+
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Varchar.into()
+    }
+
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<String, Box<dyn Error>> {
+        Ok(<&str>::read(vector, row)?.to_owned())
+    }
+}
+
+impl ScalarArg for &[u8] {
+    type Ref<'a> = &'a [u8];
+
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Blob.into()
+    }
+
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<&[u8], Box<dyn Error>> {
+        Ok(read_bytes(vector, row))
+    }
+}
+
+impl ScalarArg for Vec<u8> {
+    type Ref<'a> = Vec<u8>;
+
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Blob.into()
+    }
+
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(read_bytes(vector, row).to_vec())
+    }
+}
+
+impl<T: ScalarArg> ScalarArg for Option<T> {
+    type Ref<'a> = Option<T::Ref<'a>>;
+
+    const NULLABLE: bool = true;
+
+    fn logical_type() -> LogicalTypeHandle {
+        T::logical_type()
+    }
+
+    unsafe fn read(vector: &FlatVector, row: usize) -> Result<Self::Ref<'_>, Box<dyn Error>> {
+        if vector.row_is_null(row as u64) {
+            Ok(None)
+        } else {
+            T::read(vector, row).map(Some)
+        }
+    }
+}
+
+impl ScalarReturn for &str {
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Varchar.into()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        output.insert(row, self.as_bytes());
+        Ok(())
+    }
+}
+
+impl ScalarReturn for String {
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Varchar.into()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        self.as_str().write(output, row)
+    }
+}
+
+impl ScalarReturn for &[u8] {
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Blob.into()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        output.insert(row, self);
+        Ok(())
+    }
+}
+
+impl ScalarReturn for Vec<u8> {
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeId::Blob.into()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        self.as_slice().write(output, row)
+    }
+}
+
+impl<T: ScalarReturn> ScalarReturn for Option<T> {
+    fn logical_type() -> LogicalTypeHandle {
+        T::logical_type()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        match self {
+            Some(value) => value.write(output, row),
+            None => {
+                output.set_null(row);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<T: ScalarReturn, E: Into<Box<dyn Error>>> ScalarReturn for Result<T, E> {
+    fn logical_type() -> LogicalTypeHandle {
+        T::logical_type()
+    }
+
+    fn write(self, output: &mut FlatVector, row: usize) -> Result<(), Box<dyn Error>> {
+        self.map_err(Into::into)?.write(output, row)
+    }
+}
+
+/// A closure that can be registered with [`Connection::create_scalar_function`].
+///
+/// Implemented for `Fn`s of up to eight [`ScalarArg`]s returning a [`ScalarReturn`], `Args` is the tuple of the
+/// argument types.
+pub trait ScalarClosure<Args, R: ScalarReturn>: Send + Sync + 'static {
+    /// The DuckDB types of the arguments.
+    fn parameters() -> Vec<LogicalTypeHandle>;
+
+    /// Whether any argument accepts NULL.
+    fn accepts_null() -> bool;
+
+    /// Calls the closure for every row of the input.
+    ///
+    /// # Safety
+    ///
+    /// The input vectors must be flat vectors of the types of [`ScalarClosure::parameters`].
+    unsafe fn invoke(&self, input: &DataChunkHandle, output: &mut FlatVector) -> Result<(), Box<dyn Error>>;
+}
+
+macro_rules! impl_scalar_closure {
+    ($($arg:ident $vector:ident $idx:tt),*) => {
+        impl<F, R, $($arg),*> ScalarClosure<($($arg,)*), R> for F
+        where
+            R: ScalarReturn,
+            $($arg: ScalarArg,)*
+            F: Fn($($arg),*) -> R + for<'a> Fn($($arg::Ref<'a>),*) -> R + Send + Sync + 'static,
+        {
+            fn parameters() -> Vec<LogicalTypeHandle> {
+                vec![$($arg::logical_type()),*]
+            }
+
+            fn accepts_null() -> bool {
+                false $(|| $arg::NULLABLE)*
+            }
+
+            #[allow(unused_variables)]
+            unsafe fn invoke(&self, input: &DataChunkHandle, output: &mut FlatVector) -> Result<(), Box<dyn Error>> {
+                $(let $vector = input.flat_vector($idx);)*
+                for row in 0..input.len() {
+                    $(
+                        if !$arg::NULLABLE && $vector.row_is_null(row as u64) {
+                            output.set_null(row);
+                            continue;
+                        }
+                    )*
+                    (self)($($arg::read(&$vector, row)?),*).write(output, row)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_scalar_closure!();
+impl_scalar_closure!(A a 0);
+impl_scalar_closure!(A a 0, B b 1);
+impl_scalar_closure!(A a 0, B b 1, C c 2);
+impl_scalar_closure!(A a 0, B b 1, C c 2, D d 3);
+impl_scalar_closure!(A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_scalar_closure!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5);
+impl_scalar_closure!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5, H h 6);
+impl_scalar_closure!(A a 0, B b 1, C c 2, D d 3, E e 4, G g 5, H h 6, I i 7);
+
+unsafe extern "C" fn closure_func<F, Args, R>(
+    info: duckdb_function_info,
+    input: duckdb_data_chunk,
+    output: duckdb_vector,
+) where
+    F: ScalarClosure<Args, R>,
+    R: ScalarReturn,
+{
+    let info = ScalarFunctionInfo::from(info);
+    let input = DataChunkHandle::new_unowned(input);
+    let mut output = FlatVector::from(output);
+    let result = info.get_scalar_extra_info::<F>().invoke(&input, &mut output);
+    if let Err(e) = result {
+        info.set_error(&e.to_string());
+    }
+}
+
+impl Connection {
+    /// Register a closure as a scalar function, the signature of the function is derived from the types of the
+    /// arguments and the result of the closure.
+    ///
+    /// ```rust
+    /// # use duckdb::{Connection, Result};
+    /// fn greet(conn: &Connection) -> Result<String> {
+    ///     conn.create_scalar_function("greet", |name: &str, times: i32| -> Option<String> {
+    ///         (times > 0).then(|| format!("hello {name}").repeat(times as usize))
+    ///     })?;
+    ///     conn.query_row("SELECT greet('duck', 2)", [], |row| row.get(0))
+    /// }
+    /// # assert_eq!(greet(&Connection::open_in_memory().unwrap()).unwrap(), "hello duckhello duck");
+    /// ```
+    pub fn create_scalar_function<Args, R, F>(&self, name: &str, func: F) -> crate::Result<()>
+    where
+        F: ScalarClosure<Args, R>,
+        R: ScalarReturn,
+    {
+        self.register_scalar_function_set(ScalarFunctionSetBuilder::new(name).closure(func))
+    }
+}
+
+impl ScalarFunctionSetBuilder {
+    /// Adds a closure as an overload, the signature of the overload is derived from the types of the arguments and
+    /// the result of the closure like in [`Connection::create_scalar_function`].
+    pub fn closure<Args, R, F>(self, func: F) -> Self
+    where
+        F: ScalarClosure<Args, R>,
+        R: ScalarReturn,
+    {
+        self.push(move |scalar_function| {
+            for parameter in F::parameters() {
+                scalar_function.add_parameter(&parameter);
+            }
+            scalar_function.set_return_type(&R::logical_type());
+            scalar_function.set_function(Some(closure_func::<F, Args, R>));
+            if F::accepts_null() {
+                scalar_function.set_special_handling();
+            }
+            scalar_function.set_extra_info(func);
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use crate::Connection;
+
+    #[test]
+    fn test_closure_scalar() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.create_scalar_function("repeat_str", |s: &str, n: i32| s.repeat(n as usize))?;
+        conn.create_scalar_function("is_even", |n: i64| n % 2 == 0)?;
+        conn.create_scalar_function("answer", || 42u8)?;
+
+        let (repeated, even, answer): (String, bool, u8) = conn.query_row(
+            "select repeat_str('a long string that is not inlined ', 2), is_even(4), answer()",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!(repeated, "a long string that is not inlined ".repeat(2));
+        assert!(even);
+        assert_eq!(answer, 42);
+
+        let mut stmt =
+            conn.prepare("select repeat_str(s, n) from (values ('x', 3), (null, 1), ('y', null)) t(s, n)")?;
+        let values = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<crate::Result<Vec<Option<String>>>>()?;
+        assert_eq!(values, vec![Some("xxx".to_string()), None, None]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_closure_scalar_nulls() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.create_scalar_function("or_default", |s: Option<&str>, default: String| -> Option<String> {
+            Some(s.map_or(default, str::to_string))
+        })?;
+        conn.create_scalar_function("blob_len", |b: Option<&[u8]>| b.map(|b| b.len() as u64))?;
+
+        let mut stmt =
+            conn.prepare("select or_default(s, 'none'), blob_len(s::blob) from (values ('x'), (null)) t(s)")?;
+        let values = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<crate::Result<Vec<(String, Option<u64>)>>>()?;
+        assert_eq!(values, vec![("x".to_string(), Some(1)), ("none".to_string(), None)]);
+
+        // constant NULLs are passed to the closure as well
+        let value: String = conn.query_row("select or_default(null, 'none')", [], |row| row.get(0))?;
+        assert_eq!(value, "none");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_closure_scalar_error() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.create_scalar_function("checked_div", |a: i64, b: i64| {
+            a.checked_div(b).ok_or_else(|| format!("cannot divide {a} by {b}"))
+        })?;
+
+        let value: i64 = conn.query_row("select checked_div(7, 2)", [], |row| row.get(0))?;
+        assert_eq!(value, 3);
+
+        let err = conn
+            .query_row("select checked_div(1, 0)", [], |row| row.get::<_, i64>(0))
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot divide 1 by 0"));
+
+        Ok(())
+    }
+}
@@ -53,7 +53,8 @@ use libduckdb_sys::{
     duckdb_create_scalar_function_set, duckdb_data_chunk, duckdb_delete_callback_t, duckdb_destroy_scalar_function,
     duckdb_function_info, duckdb_scalar_function, duckdb_scalar_function_add_parameter, duckdb_scalar_function_set,
     duckdb_scalar_function_set_extra_info, duckdb_scalar_function_set_function, duckdb_scalar_function_set_name,
-    duckdb_scalar_function_set_return_type, duckdb_scalar_function_set_varargs, duckdb_vector, DuckDBSuccess,
+    duckdb_scalar_function_set_return_type, duckdb_scalar_function_set_special_handling,
+    duckdb_scalar_function_set_varargs, duckdb_scalar_function_set_volatile, duckdb_vector, DuckDBSuccess,
 };
 
 use crate::{core::LogicalTypeHandle, Error};
@@ -98,6 +99,24 @@ impl ScalarFunction {
         self
     }
 
+    /// Marks the scalar function as volatile, so DuckDB calls it for every row instead of folding it into a constant.
+    pub fn set_volatile(&self) -> &Self {
+        unsafe {
+            duckdb_scalar_function_set_volatile(self.ptr);
+        }
+        self
+    }
+
+    /// Lets the scalar function handle NULL inputs itself.
+    ///
+    /// By default the result is NULL for any row with a NULL input, without calling the function for that row.
+    pub fn set_special_handling(&self) -> &Self {
+        unsafe {
+            duckdb_scalar_function_set_special_handling(self.ptr);
+        }
+        self
+    }
+
     /// Sets the main function of the scalar function
     ///
     /// # Arguments
@@ -123,10 +142,10 @@ impl ScalarFunction {
         duckdb_scalar_function_set_extra_info(self.ptr, extra_info, destroy);
     }
 
-    pub fn set_extra_info<T: Default>(&self) -> &ScalarFunction {
+    /// Assigns the given value as extra information of the scalar function, it's dropped with the function.
+    pub fn set_extra_info<T: Send + Sync>(&self, value: T) -> &ScalarFunction {
         unsafe {
-            let t = Box::new(T::default());
-            let c_void = Box::into_raw(t) as *mut c_void;
+            let c_void = Box::into_raw(Box::new(value)) as *mut c_void;
             self.set_extra_info_impl(c_void, Some(drop_ptr::<T>));
         }
         self
@@ -1,4 +1,4 @@
-use std::ffi::CString;
+use std::{ffi::CString, sync::Arc};
 
 use function::{ScalarFunction, ScalarFunctionSet};
 use libduckdb_sys::{
@@ -12,8 +12,11 @@ use crate::{
     vtab::arrow::WritableVector,
     Connection,
 };
+mod closure;
 mod function;
 
+pub use closure::{ScalarArg, ScalarClosure, ScalarReturn};
+
 /// The duckdb Arrow scalar function interface
 #[cfg(feature = "vscalar-arrow")]
 pub mod arrow;
@@ -25,7 +28,9 @@ pub use arrow::{ArrowFunctionSignature, ArrowScalarParams, VArrowScalar};
 pub trait VScalar: Sized {
     /// State that persists across invocations of the scalar function (the lifetime of the connection)
     /// The state can be accessed by multiple threads, so it must be `Send + Sync`.
-    type State: Default + Sized + Send + Sync;
+    ///
+    /// The state is `Default::default()` unless given to [`Connection::register_scalar_function_with_state`].
+    type State: Sized + Send + Sync + 'static;
     /// The actual function
     ///
     /// # Safety
@@ -44,6 +49,18 @@ pub trait VScalar: Sized {
     /// These will result in DuckDB scalar function overloads.
     /// The invoke method should be able to handle all of these signatures.
     fn signatures() -> Vec<ScalarFunctionSignature>;
+
+    /// Whether the function is volatile, i.e. may return different results for the same inputs.
+    /// Volatile functions are never folded into a constant, e.g. to return a new random value for every row.
+    fn volatile() -> bool {
+        false
+    }
+
+    /// Whether the function handles NULL inputs itself.
+    /// Otherwise the result is NULL for any row with a NULL input and the function doesn't need to check validity.
+    fn special_null_handling() -> bool {
+        false
+    }
 }
 
 /// Duckdb scalar function parameters
@@ -125,24 +142,148 @@ where
 {
     let info = ScalarFunctionInfo::from(info);
     let mut input = DataChunkHandle::new_unowned(input);
-    let result = T::invoke(info.get_scalar_extra_info(), &mut input, &mut output);
+    let result = T::invoke(info.get_scalar_extra_info::<Arc<T::State>>(), &mut input, &mut output);
     if let Err(e) = result {
         info.set_error(&e.to_string());
     }
 }
 
-impl Connection {
-    /// Register the given ScalarFunction with the current db
-    #[inline]
-    pub fn register_scalar_function<S: VScalar>(&self, name: &str) -> crate::Result<()> {
-        let set = ScalarFunctionSet::new(name);
+/// An overload of a [`ScalarFunctionSetBuilder`], see [`ScalarFunctionSetBuilder::overload`].
+type OverloadFn =
+    Box<dyn Fn(&mut DataChunkHandle, &mut dyn WritableVector) -> Result<(), Box<dyn std::error::Error>> + Send + Sync>;
+
+unsafe extern "C" fn overload_func(info: duckdb_function_info, input: duckdb_data_chunk, mut output: duckdb_vector) {
+    let info = ScalarFunctionInfo::from(info);
+    let mut input = DataChunkHandle::new_unowned(input);
+    let result = info.get_scalar_extra_info::<OverloadFn>()(&mut input, &mut output);
+    if let Err(e) = result {
+        info.set_error(&e.to_string());
+    }
+}
+
+/// Configures the [`ScalarFunction`] of an overload when the set is built.
+type AddOverload = Box<dyn FnOnce(&ScalarFunction)>;
+
+/// A builder of a named set of scalar function overloads, each with its own implementation.
+///
+/// ```rust
+/// # use duckdb::{core::LogicalTypeId, Connection, Result};
+/// # use duckdb::vscalar::{ScalarFunctionSetBuilder, ScalarFunctionSignature};
+/// fn register_add(conn: &Connection) -> Result<()> {
+///     let set = ScalarFunctionSetBuilder::new("add")
+///         .closure(|a: i64, b: i64| a + b)
+///         .closure(|a: &str, b: &str| format!("{a}{b}"))
+///         .overload(
+///             ScalarFunctionSignature::exact(vec![LogicalTypeId::Double.into()], LogicalTypeId::Double.into()),
+///             |input, output| {
+///                 let mut output = output.flat_vector();
+///                 // SAFETY: DuckDB flattens the input of scalar functions
+///                 for (row, value) in unsafe { input.column::<f64>(0)? }.enumerate() {
+///                     match value {
+///                         Some(value) => output.as_mut_slice::<f64>()[row] = value + 1.0,
+///                         None => output.set_null(row),
+///                     }
+///                 }
+///                 Ok(())
+///             },
+///         );
+///     conn.register_scalar_function_set(set)
+/// }
+/// ```
+pub struct ScalarFunctionSetBuilder {
+    name: String,
+    overloads: Vec<AddOverload>,
+}
+
+impl ScalarFunctionSetBuilder {
+    /// Creates an empty set of scalar functions with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            overloads: Vec::new(),
+        }
+    }
+
+    fn push(mut self, overload: impl FnOnce(&ScalarFunction) + 'static) -> Self {
+        self.overloads.push(Box::new(overload));
+        self
+    }
+
+    /// Adds an overload with the given signature, implemented by `func`.
+    pub fn overload<F>(self, signature: ScalarFunctionSignature, func: F) -> Self
+    where
+        F: Fn(&mut DataChunkHandle, &mut dyn WritableVector) -> Result<(), Box<dyn std::error::Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.push(move |scalar_function| {
+            signature.register_with_scalar(scalar_function);
+            scalar_function.set_function(Some(overload_func));
+            scalar_function.set_extra_info::<OverloadFn>(Box::new(func));
+        })
+    }
+
+    /// Adds all signatures of the [`VScalar`] as overloads, with a default state.
+    pub fn scalar<S: VScalar>(self) -> Self
+    where
+        S::State: Default,
+    {
+        self.scalar_with_state::<S>(S::State::default())
+    }
+
+    /// Adds all signatures of the [`VScalar`] as overloads, which share the given state.
+    pub fn scalar_with_state<S: VScalar>(mut self, state: S::State) -> Self {
+        let state = Arc::new(state);
         for signature in S::signatures() {
-            let scalar_function = ScalarFunction::new(name)?;
-            signature.register_with_scalar(&scalar_function);
-            scalar_function.set_function(Some(scalar_func::<S>));
-            scalar_function.set_extra_info::<S::State>();
+            let state = state.clone();
+            self = self.push(move |scalar_function| {
+                signature.register_with_scalar(scalar_function);
+                scalar_function.set_function(Some(scalar_func::<S>));
+                if S::volatile() {
+                    scalar_function.set_volatile();
+                }
+                if S::special_null_handling() {
+                    scalar_function.set_special_handling();
+                }
+                scalar_function.set_extra_info(state);
+            });
+        }
+        self
+    }
+
+    fn build(self) -> crate::Result<ScalarFunctionSet> {
+        let set = ScalarFunctionSet::new(&self.name);
+        for overload in self.overloads {
+            let scalar_function = ScalarFunction::new(&self.name)?;
+            overload(&scalar_function);
             set.add_function(scalar_function)?;
         }
+        Ok(set)
+    }
+}
+
+impl Connection {
+    /// Register the given ScalarFunction with the current db
+    #[inline]
+    pub fn register_scalar_function<S: VScalar>(&self, name: &str) -> crate::Result<()>
+    where
+        S::State: Default,
+    {
+        self.register_scalar_function_set(ScalarFunctionSetBuilder::new(name).scalar::<S>())
+    }
+
+    /// Register the given ScalarFunction with the current db, with the given state instead of a default one
+    ///
+    /// This allows registering the same function several times with a different configuration.
+    #[inline]
+    pub fn register_scalar_function_with_state<S: VScalar>(&self, name: &str, state: S::State) -> crate::Result<()> {
+        self.register_scalar_function_set(ScalarFunctionSetBuilder::new(name).scalar_with_state::<S>(state))
+    }
+
+    /// Register the overloads of the given set as one scalar function with the current db
+    pub fn register_scalar_function_set(&self, set: ScalarFunctionSetBuilder) -> crate::Result<()> {
+        let set = set.build()?;
         self.db.borrow_mut().register_scalar_function_set(set)
     }
 }
@@ -156,7 +297,10 @@ impl InnerConnection {
 
 #[cfg(test)]
 mod test {
-    use std::error::Error;
+    use std::{
+        error::Error,
+        sync::atomic::{AtomicI64, Ordering},
+    };
 
     use arrow::array::Array;
     use libduckdb_sys::duckdb_string_t;
@@ -168,7 +312,7 @@ mod test {
         Connection,
     };
 
-    use super::{ScalarFunctionSignature, VScalar};
+    use super::{ScalarFunctionSetBuilder, ScalarFunctionSignature, VScalar};
 
     struct ErrorScalar {}
 
@@ -273,6 +417,135 @@ mod test {
         }
     }
 
+    #[derive(Default)]
+    struct Counter {
+        next: AtomicI64,
+    }
+
+    struct NextId {}
+
+    impl VScalar for NextId {
+        type State = Counter;
+
+        unsafe fn invoke(
+            state: &Self::State,
+            input: &mut DataChunkHandle,
+            output: &mut dyn WritableVector,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let mut output = output.flat_vector();
+            for id in output.as_mut_slice_with_len::<i64>(input.len()) {
+                *id = state.next.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(())
+        }
+
+        fn signatures() -> Vec<ScalarFunctionSignature> {
+            vec![ScalarFunctionSignature::exact(vec![], LogicalTypeId::Bigint.into())]
+        }
+
+        fn volatile() -> bool {
+            true
+        }
+    }
+
+    struct NullToZero {}
+
+    impl VScalar for NullToZero {
+        type State = ();
+
+        unsafe fn invoke(
+            _: &Self::State,
+            input: &mut DataChunkHandle,
+            output: &mut dyn WritableVector,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let values = input.flat_vector(0);
+            let mut output = output.flat_vector();
+            for row in 0..input.len() {
+                output.as_mut_slice::<i32>()[row] = if values.row_is_null(row as u64) {
+                    0
+                } else {
+                    values.as_slice::<i32>()[row]
+                };
+            }
+            Ok(())
+        }
+
+        fn signatures() -> Vec<ScalarFunctionSignature> {
+            vec![ScalarFunctionSignature::exact(
+                vec![LogicalTypeId::Integer.into()],
+                LogicalTypeId::Integer.into(),
+            )]
+        }
+
+        fn special_null_handling() -> bool {
+            true
+        }
+    }
+
+    struct ListSum {}
+
+    impl VScalar for ListSum {
+        type State = ();
+
+        unsafe fn invoke(
+            _: &Self::State,
+            input: &mut DataChunkHandle,
+            output: &mut dyn WritableVector,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let lists = input.lists(0)?;
+            let values = lists.values::<i32>()?;
+            let mut output = output.flat_vector();
+            for (row, entry) in lists.enumerate() {
+                match entry {
+                    Some(range) => {
+                        output.as_mut_slice::<i64>()[row] = range.filter_map(|i| values.get(i)).map(i64::from).sum()
+                    }
+                    None => output.set_null(row),
+                }
+            }
+            Ok(())
+        }
+
+        fn signatures() -> Vec<ScalarFunctionSignature> {
+            vec![ScalarFunctionSignature::exact(
+                vec![LogicalTypeHandle::list(&LogicalTypeId::Integer.into())],
+                LogicalTypeId::Bigint.into(),
+            )]
+        }
+    }
+
+    struct Vocabulary {
+        words: Vec<&'static str>,
+    }
+
+    struct Tokenize {}
+
+    impl VScalar for Tokenize {
+        type State = Vocabulary;
+
+        unsafe fn invoke(
+            vocabulary: &Self::State,
+            input: &mut DataChunkHandle,
+            output: &mut dyn WritableVector,
+        ) -> Result<(), Box<dyn std::error::Error>> {
+            let mut output = output.flat_vector();
+            for (row, word) in input.strings(0)?.enumerate() {
+                match word.and_then(|word| vocabulary.words.iter().position(|w| *w == word)) {
+                    Some(token) => output.as_mut_slice::<i32>()[row] = token as i32,
+                    None => output.set_null(row),
+                }
+            }
+            Ok(())
+        }
+
+        fn signatures() -> Vec<ScalarFunctionSignature> {
+            vec![ScalarFunctionSignature::exact(
+                vec![LogicalTypeId::Varchar.into()],
+                LogicalTypeId::Integer.into(),
+            )]
+        }
+    }
+
     #[test]
     fn test_scalar() -> Result<(), Box<dyn Error>> {
         let conn = Connection::open_in_memory()?;
@@ -324,4 +597,113 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_volatile_scalar() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_scalar_function::<NextId>("next_id")?;
+
+        let (count, distinct): (i64, i64) = conn.query_row(
+            "select count(id), count(distinct id) from (select next_id() as id from range(3000))",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        assert_eq!(count, 3000);
+        assert_eq!(distinct, 3000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_special_null_handling_scalar() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_scalar_function::<NullToZero>("null_to_zero")?;
+
+        let mut stmt = conn.prepare("select null_to_zero(a) from (values (1), (null), (3)) t(a)")?;
+        let values = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<crate::Result<Vec<i32>>>()?;
+        assert_eq!(values, vec![1, 0, 3]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_readers_scalar() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_scalar_function::<ListSum>("list_sum_i32")?;
+
+        let mut stmt = conn.prepare("select list_sum_i32(l) from (values ([1, 2, null]), (null), ([])) t(l)")?;
+        let values = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<crate::Result<Vec<Option<i64>>>>()?;
+        assert_eq!(values, vec![Some(3), None, Some(0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_function_set() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        let set = ScalarFunctionSetBuilder::new("describe_value")
+            .closure(|n: i64| format!("bigint {n}"))
+            .closure(|s: &str| format!("varchar {s}"))
+            .overload(
+                ScalarFunctionSignature::exact(
+                    vec![LogicalTypeId::Boolean.into(), LogicalTypeId::Boolean.into()],
+                    LogicalTypeId::Varchar.into(),
+                ),
+                |input, output| {
+                    let output = output.flat_vector();
+                    for (row, (a, b)) in unsafe { input.column::<bool>(0)? }
+                        .zip(unsafe { input.column::<bool>(1)? })
+                        .enumerate()
+                    {
+                        output.insert(row, format!("booleans {a:?} {b:?}").as_str());
+                    }
+                    Ok(())
+                },
+            )
+            .scalar::<Repeat>();
+        conn.register_scalar_function_set(set)?;
+
+        let values: (String, String, String, String) = conn.query_row(
+            "select describe_value(42), describe_value('duck'), describe_value(true, false), describe_value('ab', 2)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        assert_eq!(
+            values,
+            (
+                "bigint 42".to_string(),
+                "varchar duck".to_string(),
+                "booleans Some(true) Some(false)".to_string(),
+                "abab".to_string()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_with_state() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        let english = Vocabulary {
+            words: vec!["duck", "goose"],
+        };
+        let french = Vocabulary {
+            words: vec!["canard", "oie", "duck"],
+        };
+        conn.register_scalar_function_with_state::<Tokenize>("tokenize_en", english)?;
+        conn.register_scalar_function_with_state::<Tokenize>("tokenize_fr", french)?;
+
+        let tokens: (i32, Option<i32>, i32, i32) = conn.query_row(
+            "select tokenize_en('goose'), tokenize_en('oie'), tokenize_fr('oie'), tokenize_fr('duck')",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        assert_eq!(tokens, (1, None, 1, 2));
+
+        Ok(())
+    }
 }
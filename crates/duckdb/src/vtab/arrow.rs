@@ -1,22 +1,30 @@
 use super::{BindInfo, DataChunkHandle, InitInfo, LogicalTypeHandle, TableFunctionInfo, VTab};
-use std::sync::{atomic::AtomicBool, Arc, Mutex};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::AtomicBool, Arc, Mutex, PoisonError},
+};
 
 use crate::{
-    core::{ArrayVector, FlatVector, Inserter, ListVector, LogicalTypeId, StructVector, Vector},
+    core::{
+        ArrayVector, FlatVector, Inserter, ListVector, LogicalType, LogicalTypeId, MapVector, StructVector,
+        UnionVector, Vector,
+    },
     types::DuckString,
 };
 
 use arrow::{
     array::{
         as_boolean_array, as_generic_binary_array, as_large_list_array, as_list_array, as_map_array,
-        as_primitive_array, as_string_array, as_struct_array, Array, ArrayData, AsArray, BinaryArray, BinaryViewArray,
-        BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryArray, FixedSizeListArray, GenericBinaryBuilder,
-        GenericListArray, GenericStringArray, IntervalMonthDayNanoArray, LargeBinaryArray, LargeStringArray,
-        OffsetSizeTrait, PrimitiveArray, StringArray, StringViewArray, StructArray, TimestampMicrosecondArray,
-        TimestampNanosecondArray,
+        as_primitive_array, as_string_array, as_struct_array, Array, ArrayData, ArrayRef, AsArray, BinaryArray,
+        BinaryViewArray, BooleanArray, Date32Array, Decimal128Array, DictionaryArray, FixedSizeBinaryArray,
+        FixedSizeListArray, GenericBinaryBuilder, GenericListArray, GenericListViewArray, GenericStringArray,
+        IntervalMonthDayNanoArray, LargeBinaryArray, LargeStringArray, MapArray, OffsetSizeTrait, PrimitiveArray,
+        RunArray, StringArray, StringViewArray, StructArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+        TimestampNanosecondArray, UInt32Array, UInt64Array, UnionArray,
     },
-    buffer::{BooleanBuffer, NullBuffer},
-    compute::cast,
+    buffer::{BooleanBuffer, NullBuffer, OffsetBuffer},
+    compute::{cast, cast_with_options, take, CastOptions},
+    temporal_conversions::{MICROSECONDS, MICROSECONDS_IN_DAY, MILLISECONDS, MILLISECONDS_IN_DAY, NANOSECONDS},
 };
 
 use arrow::{
@@ -26,8 +34,9 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use libduckdb_sys::{
-    duckdb_date, duckdb_hugeint, duckdb_interval, duckdb_string_t, duckdb_time, duckdb_timestamp, duckdb_timestamp_ms,
-    duckdb_timestamp_ns, duckdb_timestamp_s, duckdb_vector,
+    duckdb_date, duckdb_enum_internal_type, duckdb_hugeint, duckdb_interval, duckdb_list_entry, duckdb_string_t,
+    duckdb_time, duckdb_timestamp, duckdb_timestamp_ms, duckdb_timestamp_ns, duckdb_timestamp_s, duckdb_vector,
+    duckdb_vector_size, DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT, DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT,
 };
 use num::{cast::AsPrimitive, ToPrimitive};
 
@@ -83,10 +92,9 @@ impl VTab for ArrowVTab {
 
         unsafe {
             let rb = address_to_arrow_record_batch(array as usize, schema as usize);
-            for f in rb.schema().fields() {
+            for (f, column) in rb.schema().fields().iter().zip(rb.columns()) {
                 let name = f.name();
-                let data_type = f.data_type();
-                let logical_type = to_duckdb_logical_type(data_type)?;
+                let logical_type = array_to_duckdb_logical_type(column.as_ref())?;
                 bind.add_result_column(name, logical_type);
             }
 
@@ -107,7 +115,7 @@ impl VTab for ArrowVTab {
         if init_info.done.load(std::sync::atomic::Ordering::Relaxed) {
             output.set_len(0);
         } else {
-            let rb = bind_info.rb.lock().unwrap();
+            let rb = bind_info.rb.lock().unwrap_or_else(PoisonError::into_inner);
             record_batch_to_duckdb_data_chunk(&rb, output)?;
             init_info.done.store(true, std::sync::atomic::Ordering::Relaxed);
         }
@@ -123,6 +131,154 @@ impl VTab for ArrowVTab {
     }
 }
 
+/// A table function that produces Arrow record batches.
+///
+/// Every `VArrowTab` is also a [`VTab`]: the schema returned by [`VArrowTab::bind`] becomes the result columns
+/// and the batches returned by [`VArrowTab::next_batch`] are split into chunks of at most the DuckDB vector size.
+pub trait VArrowTab: Sized {
+    /// The data type of the bind data.
+    ///
+    /// The bind data is shared across threads so must be `Send + Sync`.
+    type BindData: Sized + Send + Sync;
+
+    /// The data type of the init data.
+    ///
+    /// The init data is shared across threads so must be `Send + Sync`.
+    type InitData: Sized + Send + Sync;
+
+    /// Bind data to the table function
+    ///
+    /// Returns the schema of the record batches produced by [`VArrowTab::next_batch`] along with the bind data.
+    fn bind(bind: &BindInfo) -> Result<(SchemaRef, Self::BindData), Box<dyn std::error::Error>>;
+
+    /// Initialize the table function
+    fn init(init: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>>;
+
+    /// Produce the next record batch, or `None` when the table function is done.
+    ///
+    /// The batches must have the column types of the schema returned by [`VArrowTab::bind`], other batches fail the
+    /// scan. They may have any number of rows.
+    fn next_batch(
+        bind_data: &Self::BindData,
+        init_data: &Self::InitData,
+    ) -> Result<Option<RecordBatch>, Box<dyn std::error::Error>>;
+
+    /// The parameters of the table function
+    /// default is None
+    fn parameters() -> Option<Vec<LogicalTypeHandle>> {
+        None
+    }
+
+    /// The named parameters of the table function
+    /// default is None
+    fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
+        None
+    }
+}
+
+/// The bind data of a [`VArrowTab`].
+pub struct ArrowTabBindData<T: VArrowTab> {
+    schema: SchemaRef,
+    inner: T::BindData,
+}
+
+impl<T: VArrowTab> ArrowTabBindData<T> {
+    /// The bind data returned by [`VArrowTab::bind`], e.g. for [`VArrowTab::init`] through [`InitInfo::get_bind_data`].
+    pub(crate) fn inner(&self) -> &T::BindData {
+        &self.inner
+    }
+}
+
+/// The init data of a [`VArrowTab`], keeps track of the batch that is partially emitted.
+pub struct ArrowTabInitData<T: VArrowTab> {
+    inner: T::InitData,
+    pending: Mutex<Option<(RecordBatch, usize)>>,
+}
+
+impl<T> VTab for T
+where
+    T: VArrowTab,
+{
+    type BindData = ArrowTabBindData<T>;
+    type InitData = ArrowTabInitData<T>;
+
+    fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
+        let (schema, inner) = T::bind(bind)?;
+        for field in schema.fields() {
+            bind.add_result_column(field.name(), to_duckdb_logical_type(field.data_type())?);
+        }
+        Ok(ArrowTabBindData { schema, inner })
+    }
+
+    fn init(init: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        Ok(ArrowTabInitData {
+            inner: T::init(init)?,
+            pending: Mutex::new(None),
+        })
+    }
+
+    fn func(func: &TableFunctionInfo<Self>, output: &mut DataChunkHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let bind_data = func.get_bind_data();
+        let init_data = func.get_init_data();
+        let mut pending = init_data.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+        loop {
+            let (batch, offset) = match pending.as_mut() {
+                Some((batch, offset)) if *offset < batch.num_rows() => (batch, offset),
+                _ => match T::next_batch(&bind_data.inner, &init_data.inner)? {
+                    Some(batch) => {
+                        check_batch_schema(&batch, &bind_data.schema)?;
+                        *pending = Some((batch, 0));
+                        continue;
+                    }
+                    None => {
+                        *pending = None;
+                        output.set_len(0);
+                        return Ok(());
+                    }
+                },
+            };
+
+            let len = (batch.num_rows() - *offset).min(unsafe { duckdb_vector_size() } as usize);
+            record_batch_to_duckdb_data_chunk(&batch.slice(*offset, len), output)?;
+            *offset += len;
+            return Ok(());
+        }
+    }
+
+    fn parameters() -> Option<Vec<LogicalTypeHandle>> {
+        T::parameters()
+    }
+
+    fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
+        T::named_parameters()
+    }
+}
+
+/// Check that a batch has the columns of the bind schema, the output vectors are typed by the bind schema.
+fn check_batch_schema(batch: &RecordBatch, schema: &SchemaRef) -> Result<(), Box<dyn std::error::Error>> {
+    if batch.num_columns() != schema.fields().len() {
+        return Err(format!(
+            "record batch has {} columns, expected {}",
+            batch.num_columns(),
+            schema.fields().len()
+        )
+        .into());
+    }
+    for (column, field) in batch.columns().iter().zip(schema.fields()) {
+        if !column.data_type().equals_datatype(field.data_type()) {
+            return Err(format!(
+                "column \"{}\" of the record batch is {}, expected {}",
+                field.name(),
+                column.data_type(),
+                field.data_type()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
 /// Convert arrow DataType to duckdb type id
 pub fn to_duckdb_type_id(data_type: &DataType) -> Result<LogicalTypeId, Box<dyn std::error::Error>> {
     use LogicalTypeId::*;
@@ -137,7 +293,7 @@ pub fn to_duckdb_type_id(data_type: &DataType) -> Result<LogicalTypeId, Box<dyn
         DataType::UInt16 => USmallint,
         DataType::UInt32 => UInteger,
         DataType::UInt64 => UBigint,
-        DataType::Float32 => Float,
+        DataType::Float16 | DataType::Float32 => Float,
         DataType::Float64 => Double,
         DataType::Timestamp(unit, None) => match unit {
             TimeUnit::Second => TimestampS,
@@ -154,12 +310,18 @@ pub fn to_duckdb_type_id(data_type: &DataType) -> Result<LogicalTypeId, Box<dyn
         DataType::Interval(_) => Interval,
         DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) | DataType::BinaryView => Blob,
         DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => Varchar,
-        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _) => List,
+        DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::ListView(_)
+        | DataType::LargeListView(_)
+        | DataType::FixedSizeList(_, _) => List,
         DataType::Struct(_) => Struct,
         DataType::Union(_, _) => Union,
-        // DataType::Dictionary(_, _) => todo!(),
+        DataType::Dictionary(_, value_type) => to_duckdb_type_id(value_type)?,
         DataType::Decimal128(_, _) => Decimal,
-        DataType::Decimal256(_, _) => Double,
+        DataType::Decimal256(width, _) if *width <= MAX_DECIMAL_WIDTH => Decimal,
+        DataType::Decimal256(width, _) => return Err(decimal_width_error(data_type, *width)),
+        DataType::RunEndEncoded(_, values) => to_duckdb_type_id(values.data_type())?,
         DataType::Map(_, _) => Map,
         _ => {
             return Err(format!("Unsupported data type: {:?}", data_type).into());
@@ -193,11 +355,12 @@ pub fn to_duckdb_logical_type(data_type: &DataType) -> Result<LogicalTypeHandle,
             for field in fields.iter() {
                 shape.push((field.name().as_str(), to_duckdb_logical_type(field.data_type())?));
             }
-            Ok(LogicalTypeHandle::struct_type(shape.as_slice()))
-        }
-        DataType::List(child) | DataType::LargeList(child) => {
-            Ok(LogicalTypeHandle::list(&to_duckdb_logical_type(child.data_type())?))
+            Ok(LogicalTypeHandle::try_struct_type(shape.as_slice())?)
         }
+        DataType::List(child)
+        | DataType::LargeList(child)
+        | DataType::ListView(child)
+        | DataType::LargeListView(child) => Ok(LogicalTypeHandle::list(&to_duckdb_logical_type(child.data_type())?)),
         DataType::FixedSizeList(child, array_size) => Ok(LogicalTypeHandle::array(
             &to_duckdb_logical_type(child.data_type())?,
             *array_size as u64,
@@ -210,7 +373,26 @@ pub fn to_duckdb_logical_type(data_type: &DataType) -> Result<LogicalTypeHandle,
             }
             Ok(LogicalTypeHandle::decimal(*width, (*scale).try_into().unwrap()))
         }
+        DataType::Decimal256(width, scale) => {
+            if *width > MAX_DECIMAL_WIDTH {
+                return Err(decimal_width_error(data_type, *width));
+            }
+            if *scale < 0 {
+                return Err(
+                    format!("Unsupported data type: {data_type}, negative decimal scale is not supported").into(),
+                );
+            }
+            Ok(LogicalTypeHandle::decimal(*width, (*scale).try_into().unwrap()))
+        }
+        DataType::RunEndEncoded(_, values) => to_duckdb_logical_type(values.data_type()),
         DataType::Map(field, _) => arrow_map_to_duckdb_logical_type(field),
+        DataType::Union(fields, _) => {
+            let mut members = vec![];
+            for (_, field) in fields.iter() {
+                members.push((field.name().as_str(), to_duckdb_logical_type(field.data_type())?));
+            }
+            Ok(LogicalTypeHandle::try_union_type(members.as_slice())?)
+        }
         DataType::Boolean
         | DataType::Utf8
         | DataType::LargeUtf8
@@ -227,6 +409,78 @@ pub fn to_duckdb_logical_type(data_type: &DataType) -> Result<LogicalTypeHandle,
     }
 }
 
+impl TryFrom<&DataType> for LogicalType {
+    type Error = Box<dyn std::error::Error>;
+
+    /// The logical type an arrow type is imported as, see [`to_duckdb_logical_type`]
+    fn try_from(data_type: &DataType) -> Result<Self, Self::Error> {
+        Ok(Self::try_from(&to_duckdb_logical_type(data_type)?)?)
+    }
+}
+
+/// The maximum width of a duckdb `DECIMAL`.
+const MAX_DECIMAL_WIDTH: u8 = 38;
+
+fn decimal_width_error(data_type: &DataType, width: u8) -> Box<dyn std::error::Error> {
+    format!(
+        "Unsupported data type: {data_type}, precision {width} would be lost as DECIMAL supports at most {MAX_DECIMAL_WIDTH} digits"
+    )
+    .into()
+}
+
+/// Convert an arrow array to a duckdb logical type
+///
+/// Unlike [`to_duckdb_logical_type`] this looks at the dictionaries of the array, so dictionary-encoded strings become
+/// an `ENUM` of the dictionary values instead of being expanded to `VARCHAR`. The `ENUM` only has the values of this
+/// array, so a column that receives several arrays should use [`to_duckdb_logical_type`].
+pub fn array_to_duckdb_logical_type(array: &dyn Array) -> Result<LogicalTypeHandle, Box<dyn std::error::Error>> {
+    match array.data_type() {
+        DataType::Dictionary(_, _) => match dictionary_enum_members(array.as_any_dictionary().values().as_ref()) {
+            Some(members) => Ok(LogicalTypeHandle::enum_type(
+                &members.iter().map(String::as_str).collect::<Vec<_>>(),
+            )?),
+            None => to_duckdb_logical_type(array.data_type()),
+        },
+        DataType::Struct(fields) => {
+            let mut shape = vec![];
+            for (field, column) in fields.iter().zip(as_struct_array(array).columns()) {
+                shape.push((field.name().as_str(), array_to_duckdb_logical_type(column.as_ref())?));
+            }
+            Ok(LogicalTypeHandle::try_struct_type(shape.as_slice())?)
+        }
+        DataType::List(_) => Ok(LogicalTypeHandle::list(&array_to_duckdb_logical_type(
+            as_list_array(array).values().as_ref(),
+        )?)),
+        DataType::LargeList(_) => Ok(LogicalTypeHandle::list(&array_to_duckdb_logical_type(
+            as_large_list_array(array).values().as_ref(),
+        )?)),
+        data_type => to_duckdb_logical_type(data_type),
+    }
+}
+
+/// Returns the values of a dictionary as the members of a duckdb `ENUM`.
+///
+/// Returns `None` if the values are not strings, or contain nulls or duplicates, as an `ENUM` can't represent them.
+/// An empty dictionary, e.g. of an all-NULL column, is `None` too, an `ENUM` needs members.
+fn dictionary_enum_members(values: &dyn Array) -> Option<Vec<String>> {
+    if !matches!(
+        values.data_type(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    ) || values.null_count() > 0
+        || values.is_empty()
+    {
+        return None;
+    }
+    let values = cast(values, &DataType::Utf8).ok()?;
+    let members: Vec<String> = as_string_array(values.as_ref())
+        .iter()
+        .flatten()
+        .map(String::from)
+        .collect();
+    let unique: HashSet<&str> = members.iter().map(String::as_str).collect();
+    (unique.len() == members.len()).then_some(members)
+}
+
 fn arrow_map_to_duckdb_logical_type(field: &FieldRef) -> Result<LogicalTypeHandle, Box<dyn std::error::Error>> {
     // Map is a logical nested type that is represented as `List<entries: Struct<key: K, value: V>>`
     let DataType::Struct(ref fields) = field.data_type() else {
@@ -251,8 +505,8 @@ fn arrow_map_to_duckdb_logical_type(field: &FieldRef) -> Result<LogicalTypeHandl
     };
 
     Ok(LogicalTypeHandle::map(
-        &LogicalTypeHandle::from(to_duckdb_type_id(key_field.data_type())?),
-        &LogicalTypeHandle::from(to_duckdb_type_id(value_field.data_type())?),
+        &to_duckdb_logical_type(key_field.data_type())?,
+        &to_duckdb_logical_type(value_field.data_type())?,
     ))
 }
 
@@ -497,41 +751,224 @@ pub fn flat_vector_to_arrow_array(
 
             Ok(Arc::new(structs))
         }
-        LogicalTypeId::Struct => {
-            todo!()
-        }
-        LogicalTypeId::Decimal => {
-            todo!()
-        }
+        LogicalTypeId::Struct => Ok(Arc::new(struct_vector_to_arrow_array(vector, len)?)),
+        LogicalTypeId::Decimal => decimal_vector_to_arrow_array(vector, len),
         LogicalTypeId::Map => {
-            todo!()
+            let (offsets, entries, nulls) = list_vector_to_arrow_parts(vector, len)?;
+            let entries = as_struct_array(entries.as_ref()).clone();
+            let field = Arc::new(Field::new("entries", entries.data_type().clone(), false));
+            Ok(Arc::new(MapArray::try_new(field, offsets, entries, nulls, false)?))
         }
         LogicalTypeId::List => {
-            todo!()
-        }
-        LogicalTypeId::Union => {
-            todo!()
+            let (offsets, values, nulls) = list_vector_to_arrow_parts(vector, len)?;
+            let field = Arc::new(Field::new("l", values.data_type().clone(), true));
+            Ok(Arc::new(GenericListArray::<i32>::try_new(
+                field, offsets, values, nulls,
+            )?))
+        }
+        LogicalTypeId::Array => {
+            let array_vector = ArrayVector::from(vector.unowned_ptr());
+            let size = array_vector.get_array_size() as usize;
+            let mut child = array_vector.child(len * size);
+            let values = flat_vector_to_arrow_array(&mut child, len * size)?;
+            let field = Arc::new(Field::new("", values.data_type().clone(), true));
+            Ok(Arc::new(FixedSizeListArray::try_new(
+                field,
+                size as i32,
+                values,
+                vector_nulls(vector, len),
+            )?))
         }
         LogicalTypeId::Interval => {
-            let _data = vector.as_slice_with_len::<duckdb_interval>(len);
-            todo!()
+            let data = vector.as_slice_with_len::<duckdb_interval>(len);
+            let values = data
+                .iter()
+                .map(|duckdb_interval { months, days, micros }| {
+                    let nanos = micros
+                        .checked_mul(1000)
+                        .ok_or_else(|| format!("interval of {micros} microseconds overflows nanoseconds"))?;
+                    Ok(IntervalMonthDayNanoType::make_value(*months, *days, nanos))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            Ok(Arc::new(IntervalMonthDayNanoArray::from_iter_values_with_nulls(
+                values,
+                vector_nulls(vector, len),
+            )))
         }
         LogicalTypeId::Hugeint => {
-            let _data = vector.as_slice_with_len::<duckdb_hugeint>(len);
-            todo!()
-        }
-        LogicalTypeId::Enum => {
-            todo!()
-        }
-        LogicalTypeId::Uuid => {
-            todo!()
+            let data = vector.as_slice_with_len::<duckdb_hugeint>(len);
+
+            Ok(Arc::new(
+                Decimal128Array::from_iter_values_with_nulls(
+                    data.iter().map(hugeint_to_i128),
+                    vector_nulls(vector, len),
+                )
+                .with_precision_and_scale(MAX_DECIMAL_WIDTH, 0)?,
+            ))
         }
-        LogicalTypeId::Invalid => {
-            todo!()
+        LogicalTypeId::Enum => Ok(Arc::new(enum_vector_to_arrow_array(vector, len)?)),
+        id => Err(format!("Unsupported logical type: {id:?}").into()),
+    }
+}
+
+/// Checks that [`flat_vector_to_arrow_array`] can convert vectors of the logical type, including its children.
+pub(crate) fn check_arrow_readable(logical_type: &LogicalTypeHandle) -> Result<(), Box<dyn std::error::Error>> {
+    match logical_type.id() {
+        LogicalTypeId::Struct => {
+            (0..logical_type.num_children()).try_for_each(|idx| check_arrow_readable(&logical_type.child(idx)))
         }
+        LogicalTypeId::List => check_arrow_readable(&logical_type.list_child_type()),
+        LogicalTypeId::Array => check_arrow_readable(&logical_type.array_child_type()),
+        LogicalTypeId::Map => {
+            check_arrow_readable(&logical_type.map_key_type())?;
+            check_arrow_readable(&logical_type.map_value_type())
+        }
+        LogicalTypeId::SQLNull
+        | LogicalTypeId::Boolean
+        | LogicalTypeId::Tinyint
+        | LogicalTypeId::Smallint
+        | LogicalTypeId::Integer
+        | LogicalTypeId::Bigint
+        | LogicalTypeId::UTinyint
+        | LogicalTypeId::USmallint
+        | LogicalTypeId::UInteger
+        | LogicalTypeId::UBigint
+        | LogicalTypeId::Hugeint
+        | LogicalTypeId::Float
+        | LogicalTypeId::Double
+        | LogicalTypeId::Decimal
+        | LogicalTypeId::Timestamp
+        | LogicalTypeId::TimestampS
+        | LogicalTypeId::TimestampMs
+        | LogicalTypeId::TimestampNs
+        | LogicalTypeId::TimestampTZ
+        | LogicalTypeId::Date
+        | LogicalTypeId::Time
+        | LogicalTypeId::Interval
+        | LogicalTypeId::Varchar
+        | LogicalTypeId::Blob
+        | LogicalTypeId::Enum => Ok(()),
+        id => Err(format!("Unsupported logical type: {id:?}").into()),
     }
 }
 
+/// The validity of the first `len` rows of the vector.
+fn vector_nulls(vector: &FlatVector, len: usize) -> Option<NullBuffer> {
+    Some(NullBuffer::new(BooleanBuffer::collect_bool(len, |row| {
+        !vector.row_is_null(row as u64)
+    })))
+}
+
+fn hugeint_to_i128(value: &duckdb_hugeint) -> i128 {
+    ((value.upper as i128) << 64) | value.lower as i128
+}
+
+fn struct_vector_to_arrow_array(vector: &FlatVector, len: usize) -> Result<StructArray, Box<dyn std::error::Error>> {
+    let struct_vector = StructVector::from(vector.unowned_ptr());
+    let mut fields = vec![];
+    let mut columns = vec![];
+    for idx in 0..struct_vector.num_children() {
+        let mut child = struct_vector.child(idx, len);
+        let column = flat_vector_to_arrow_array(&mut child, len)?;
+        fields.push(Field::new(
+            struct_vector.child_name(idx).to_string_lossy(),
+            column.data_type().clone(),
+            true,
+        ));
+        columns.push(column);
+    }
+    Ok(StructArray::try_new(fields.into(), columns, vector_nulls(vector, len))?)
+}
+
+/// The offsets, values and validity of an arrow list.
+type ListParts = (OffsetBuffer<i32>, ArrayRef, Option<NullBuffer>);
+
+/// Converts the first `len` rows of a `LIST` or `MAP` vector to the parts of an arrow list.
+///
+/// The entries of a duckdb list may overlap or be out of order, in which case the values are copied in row order.
+fn list_vector_to_arrow_parts(vector: &FlatVector, len: usize) -> Result<ListParts, Box<dyn std::error::Error>> {
+    let list_vector = ListVector::from(vector.unowned_ptr());
+    let mut child = list_vector.child(list_vector.len());
+    let values = flat_vector_to_arrow_array(&mut child, list_vector.len())?;
+
+    let entries = vector.as_slice_with_len::<duckdb_list_entry>(len);
+    let nulls = vector_nulls(vector, len);
+    let ranges = entries.iter().enumerate().map(|(row, entry)| {
+        if vector.row_is_null(row as u64) {
+            0..0
+        } else {
+            entry.offset as usize..(entry.offset + entry.length) as usize
+        }
+    });
+
+    let start = ranges
+        .clone()
+        .find(|range| !range.is_empty())
+        .map_or(0, |range| range.start);
+    let mut end = start;
+    let contiguous = ranges.clone().all(|range| {
+        let follows = range.is_empty() || range.start == end;
+        end = end.max(range.end);
+        follows
+    });
+    let lengths = ranges.clone().map(|range| range.len());
+    let offsets = OffsetBuffer::<i32>::from_lengths(lengths);
+    if contiguous {
+        return Ok((offsets, values.slice(start, end - start), nulls));
+    }
+    let indices = UInt64Array::from_iter_values(ranges.flatten().map(|idx| idx as u64));
+    Ok((offsets, take(values.as_ref(), &indices, None)?, nulls))
+}
+
+fn decimal_vector_to_arrow_array(vector: &FlatVector, len: usize) -> Result<ArrayRef, Box<dyn std::error::Error>> {
+    let logical_type = vector.logical_type();
+    let (width, scale) = (logical_type.decimal_width(), logical_type.decimal_scale());
+    let nulls = vector_nulls(vector, len);
+    // the physical type of a decimal depends on its width
+    let values: Decimal128Array = match width {
+        0..=4 => Decimal128Array::from_iter_values_with_nulls(
+            vector.as_slice_with_len::<i16>(len).iter().map(|v| *v as i128),
+            nulls,
+        ),
+        5..=9 => Decimal128Array::from_iter_values_with_nulls(
+            vector.as_slice_with_len::<i32>(len).iter().map(|v| *v as i128),
+            nulls,
+        ),
+        10..=18 => Decimal128Array::from_iter_values_with_nulls(
+            vector.as_slice_with_len::<i64>(len).iter().map(|v| *v as i128),
+            nulls,
+        ),
+        _ => Decimal128Array::from_iter_values_with_nulls(
+            vector
+                .as_slice_with_len::<duckdb_hugeint>(len)
+                .iter()
+                .map(hugeint_to_i128),
+            nulls,
+        ),
+    };
+    Ok(Arc::new(values.with_precision_and_scale(width, scale as i8)?))
+}
+
+/// Converts an `ENUM` vector to a dictionary of its members.
+fn enum_vector_to_arrow_array(
+    vector: &FlatVector,
+    len: usize,
+) -> Result<DictionaryArray<UInt32Type>, Box<dyn std::error::Error>> {
+    let logical_type = vector.logical_type();
+    let members = (0..logical_type.enum_dictionary_size())
+        .map(|idx| logical_type.enum_dictionary_value(idx))
+        .collect::<Vec<_>>();
+    // the physical type of an enum depends on the number of members
+    let keys: Vec<u32> = match unsafe { duckdb_enum_internal_type(logical_type.ptr) } {
+        DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => vector.as_slice_with_len::<u8>(len).iter().map(|k| *k as u32).collect(),
+        DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => vector.as_slice_with_len::<u16>(len).iter().map(|k| *k as u32).collect(),
+        _ => vector.as_slice_with_len::<u32>(len).to_vec(),
+    };
+    let keys = UInt32Array::new(keys.into(), vector_nulls(vector, len));
+    Ok(DictionaryArray::try_new(keys, Arc::new(StringArray::from(members)))?)
+}
+
 /// converts a `DataChunk` to arrow `RecordBatch`
 pub fn data_chunk_to_arrow(chunk: &DataChunkHandle) -> Result<RecordBatch, Box<dyn std::error::Error>> {
     let len = chunk.len();
@@ -577,6 +1014,14 @@ impl WritableVector for DataChunkHandleSlice<'_> {
     fn list_vector(&mut self) -> ListVector {
         self.chunk.list_vector(self.column_index)
     }
+
+    fn map_vector(&mut self) -> MapVector {
+        self.chunk.map_vector(self.column_index)
+    }
+
+    fn union_vector(&mut self) -> UnionVector {
+        self.chunk.union_vector(self.column_index)
+    }
 }
 
 /// A WriteableVector is a trait that allows writing data to a DuckDB vector.
@@ -591,6 +1036,14 @@ pub trait WritableVector {
     fn array_vector(&mut self) -> ArrayVector;
     /// Get the vector as a `StructVector`.
     fn struct_vector(&mut self) -> StructVector;
+    /// Get the vector as a `MapVector`, by default the [`WritableVector::list_vector`] that stores the map.
+    fn map_vector(&mut self) -> MapVector {
+        MapVector::from(self.list_vector())
+    }
+    /// Get the vector as a `UnionVector`, by default the [`WritableVector::struct_vector`] that stores the union.
+    fn union_vector(&mut self) -> UnionVector {
+        UnionVector::from(self.struct_vector())
+    }
 }
 
 impl WritableVector for FlatVector {
@@ -679,18 +1132,29 @@ pub fn write_arrow_array_to_vector(
             let mut struct_vector = chunk.struct_vector();
             struct_array_to_vector(struct_array, &mut struct_vector)?;
         }
+        DataType::Dictionary(_, _) => {
+            dictionary_array_to_vector(col.as_ref(), &mut chunk.flat_vector())?;
+        }
+        DataType::ListView(_) => {
+            list_view_array_to_vector(col.as_list_view::<i32>(), &mut chunk.list_vector())?;
+        }
+        DataType::LargeListView(_) => {
+            list_view_array_to_vector(col.as_list_view::<i64>(), &mut chunk.list_vector())?;
+        }
+        DataType::RunEndEncoded(run_ends, _) => {
+            let values = match run_ends.data_type() {
+                DataType::Int16 => run_array_to_values(col.as_run::<Int16Type>())?,
+                DataType::Int32 => run_array_to_values(col.as_run::<Int32Type>())?,
+                DataType::Int64 => run_array_to_values(col.as_run::<Int64Type>())?,
+                dt => return Err(format!("Run ends of type {dt} are not supported").into()),
+            };
+            write_arrow_array_to_vector(&values, chunk)?;
+        }
         DataType::Map(_, _) => {
-            // [`MapArray`] is physically a [`ListArray`] of key values pairs stored as an `entries` [`StructArray`] with 2 child fields.
-            let map_array = as_map_array(col.as_ref());
-            let out = &mut chunk.list_vector();
-            struct_array_to_vector(map_array.entries(), &mut out.struct_child(map_array.entries().len()))?;
-
-            for i in 0..map_array.len() {
-                let offset = map_array.value_offsets()[i];
-                let length = map_array.value_length(i);
-                out.set_entry(i, offset.as_(), length.as_());
-            }
-            set_nulls_in_list_vector(map_array, out);
+            map_array_to_vector(as_map_array(col.as_ref()), &mut chunk.map_vector())?;
+        }
+        DataType::Union(_, _) => {
+            union_array_to_vector(col.as_union(), &mut chunk.union_vector())?;
         }
         dt => {
             return Err(format!(
@@ -720,6 +1184,14 @@ impl WritableVector for duckdb_vector {
     fn struct_vector(&mut self) -> StructVector {
         StructVector::from(*self)
     }
+
+    fn map_vector(&mut self) -> MapVector {
+        MapVector::from(*self)
+    }
+
+    fn union_vector(&mut self) -> UnionVector {
+        UnionVector::from(*self)
+    }
 }
 
 /// Converts a `RecordBatch` to a `DataChunk` in the DuckDB format.
@@ -829,6 +1301,8 @@ fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<
                 out.as_mut_any().downcast_mut().unwrap(),
             );
         }
+        // every half-precision float is exactly representable as a float
+        DataType::Float16 => primitive_array_to_flat_vector_cast::<Float32Type>(Float32Type::DATA_TYPE, array, out),
         DataType::Decimal128(width, _) => {
             decimal_array_to_vector(
                 as_primitive_array(array),
@@ -836,18 +1310,35 @@ fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<
                 *width,
             );
         }
-        DataType::Interval(_) | DataType::Duration(_) => {
-            let array = IntervalMonthDayNanoArray::from(
-                cast(array, &DataType::Interval(IntervalUnit::MonthDayNano))
-                    .expect("array is casted into IntervalMonthDayNanoArray")
-                    .as_primitive::<IntervalMonthDayNanoType>()
-                    .values()
-                    .iter()
-                    .map(|a| IntervalMonthDayNanoType::make_value(a.months, a.days, a.nanoseconds / 1000))
-                    .collect::<Vec<_>>(),
+        DataType::Decimal256(width, scale) => {
+            if *width > MAX_DECIMAL_WIDTH {
+                return Err(decimal_width_error(array.data_type(), *width));
+            }
+            let array = cast_with_options(
+                array,
+                &DataType::Decimal128(*width, *scale),
+                &CastOptions {
+                    safe: false,
+                    ..Default::default()
+                },
+            )?;
+            // values that don't respect the width would be truncated by duckdb
+            array
+                .as_primitive::<Decimal128Type>()
+                .validate_decimal_precision(*width)?;
+            decimal_array_to_vector(
+                as_primitive_array(&array),
+                out.as_mut_any().downcast_mut().unwrap(),
+                *width,
             );
+        }
+        DataType::Interval(_) | DataType::Duration(_) => {
+            let array: IntervalMonthDayNanoArray = cast(array, &DataType::Interval(IntervalUnit::MonthDayNano))
+                .expect("array is casted into IntervalMonthDayNanoArray")
+                .as_primitive::<IntervalMonthDayNanoType>()
+                .unary(|a| IntervalMonthDayNanoType::make_value(a.months, a.days, a.nanoseconds / 1000));
             primitive_array_to_flat_vector::<IntervalMonthDayNanoType>(
-                as_primitive_array(&array),
+                &array,
                 out.as_mut_any().downcast_mut().unwrap(),
             );
         }
@@ -881,18 +1372,95 @@ fn primitive_array_to_vector(array: &dyn Array, out: &mut dyn Vector) -> Result<
                 out.as_mut_any().downcast_mut().unwrap(),
             );
         }
-        DataType::Date64 => primitive_array_to_flat_vector_cast::<Date32Type>(Date32Type::DATA_TYPE, array, out),
-        DataType::Time32(_) => {
-            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)
-        }
-        DataType::Time64(_) => {
-            primitive_array_to_flat_vector_cast::<Time64MicrosecondType>(Time64MicrosecondType::DATA_TYPE, array, out)
+        DataType::Date64 => {
+            let days = checked_unary(array.as_primitive::<Date64Type>(), |millis| {
+                if millis % MILLISECONDS_IN_DAY != 0 {
+                    return Err(format!("Date64 value {millis} is not a whole number of days"));
+                }
+                i32::try_from(millis / MILLISECONDS_IN_DAY)
+                    .map_err(|_| format!("Date64 value {millis} is out of range for DATE"))
+            })?;
+            primitive_array_to_flat_vector::<Date32Type>(&days, out.as_mut_any().downcast_mut().unwrap());
+        }
+        DataType::Time32(unit) => {
+            let micros = match unit {
+                TimeUnit::Second => time_to_micros(array.as_primitive::<Time32SecondType>(), MICROSECONDS)?,
+                _ => time_to_micros(
+                    array.as_primitive::<Time32MillisecondType>(),
+                    MICROSECONDS / MILLISECONDS,
+                )?,
+            };
+            primitive_array_to_flat_vector::<Time64MicrosecondType>(&micros, out.as_mut_any().downcast_mut().unwrap());
+        }
+        DataType::Time64(unit) => {
+            let micros = match unit {
+                TimeUnit::Microsecond => time_to_micros(array.as_primitive::<Time64MicrosecondType>(), 1)?,
+                _ => checked_unary(array.as_primitive::<Time64NanosecondType>(), |nanos| {
+                    if nanos % NANOSECONDS_IN_MICROSECOND != 0 {
+                        return Err(format!("Time64 value {nanos}ns would lose precision as TIME"));
+                    }
+                    check_time_of_day(nanos / NANOSECONDS_IN_MICROSECOND)
+                })?,
+            };
+            primitive_array_to_flat_vector::<Time64MicrosecondType>(&micros, out.as_mut_any().downcast_mut().unwrap());
         }
         datatype => return Err(format!("Data type \"{datatype}\" not yet supported by ArrowVTab").into()),
     }
     Ok(())
 }
 
+const NANOSECONDS_IN_MICROSECOND: i64 = NANOSECONDS / MICROSECONDS;
+
+/// Apply a fallible conversion to the valid values of a primitive array, keeping its nulls.
+fn checked_unary<I, O, F>(array: &PrimitiveArray<I>, op: F) -> Result<PrimitiveArray<O>, Box<dyn std::error::Error>>
+where
+    I: ArrowPrimitiveType,
+    O: ArrowPrimitiveType,
+    F: Fn(I::Native) -> Result<O::Native, String>,
+{
+    let mut values = Vec::with_capacity(array.len());
+    for (i, value) in array.values().iter().enumerate() {
+        values.push(if array.is_null(i) {
+            O::Native::default()
+        } else {
+            op(*value)?
+        });
+    }
+    Ok(PrimitiveArray::<O>::new(values.into(), array.nulls().cloned()))
+}
+
+/// Check that a time in microseconds is within a day, as duckdb `TIME` only supports `00:00:00` to `24:00:00`.
+fn check_time_of_day(micros: i64) -> Result<i64, String> {
+    if (0..=MICROSECONDS_IN_DAY).contains(&micros) {
+        Ok(micros)
+    } else {
+        Err(format!("time value {micros}us is out of range for TIME"))
+    }
+}
+
+/// Convert a `Time32`/`Time64` array to microseconds, `factor` being the number of microseconds per unit.
+fn time_to_micros<T>(
+    array: &PrimitiveArray<T>,
+    factor: i64,
+) -> Result<Time64MicrosecondArray, Box<dyn std::error::Error>>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Into<i64>,
+{
+    checked_unary(array, |value| check_time_of_day(value.into() * factor))
+}
+
+/// Expand a run-end encoded array to its logical values.
+fn run_array_to_values<R: RunEndIndexType>(array: &RunArray<R>) -> Result<ArrayRef, Box<dyn std::error::Error>> {
+    let run_ends = array.run_ends();
+    let mut indices = Vec::with_capacity(array.len());
+    for physical in run_ends.get_start_physical_index()..=run_ends.get_end_physical_index() {
+        let run_end = (run_ends.values()[physical].as_usize() - run_ends.offset()).min(array.len());
+        indices.resize(run_end, physical as u32);
+    }
+    Ok(take(array.values().as_ref(), &UInt32Array::from(indices), None)?)
+}
+
 /// Convert Arrow [Decimal128Array] to a duckdb vector.
 fn decimal_array_to_vector(array: &Decimal128Array, out: &mut FlatVector, width: u8) {
     match width {
@@ -928,6 +1496,70 @@ fn decimal_array_to_vector(array: &Decimal128Array, out: &mut FlatVector, width:
     set_nulls_in_flat_vector(array, out);
 }
 
+/// Convert Arrow dictionary array to a duckdb vector.
+///
+/// An `ENUM` vector only receives the keys, mapped to the members of the `ENUM`; any other vector receives the
+/// expanded values.
+fn dictionary_array_to_vector(array: &dyn Array, out: &mut FlatVector) -> Result<(), Box<dyn std::error::Error>> {
+    let dictionary = array.as_any_dictionary();
+    let logical_type = out.logical_type();
+    if logical_type.id() != LogicalTypeId::Enum {
+        let values = cast(array, dictionary.values().data_type())?;
+        match values.data_type() {
+            dt if dt.is_primitive() || matches!(dt, DataType::Boolean) => primitive_array_to_vector(&values, out)?,
+            DataType::Utf8 => string_array_to_vector(as_string_array(&values), out),
+            DataType::LargeUtf8 => string_array_to_vector(values.as_string::<i64>(), out),
+            DataType::Utf8View => string_view_array_to_vector(values.as_string_view(), out),
+            DataType::Binary => binary_array_to_vector(values.as_binary::<i32>(), out),
+            DataType::LargeBinary => large_binary_array_to_vector(values.as_binary::<i64>(), out),
+            dt => return Err(format!("Dictionary with values of type '{dt}' is not supported yet").into()),
+        }
+        return Ok(());
+    }
+
+    // position of every dictionary value among the members of the enum
+    let members: HashMap<String, u32> = (0..logical_type.enum_dictionary_size())
+        .map(|i| (logical_type.enum_dictionary_value(i), i as u32))
+        .collect();
+    let values = cast(dictionary.values(), &DataType::Utf8)?;
+    let positions = as_string_array(&values)
+        .iter()
+        .map(|value| match value.and_then(|value| members.get(value)) {
+            Some(position) => Ok(*position),
+            None => Err(format!("Dictionary value {value:?} is not a member of the enum")),
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    // without values every key is NULL, and `normalized_keys` requires values
+    if dictionary.values().is_empty() {
+        set_nulls_in_flat_vector(array, out);
+        return Ok(());
+    }
+    let keys = dictionary.normalized_keys();
+    match unsafe { duckdb_enum_internal_type(logical_type.ptr) } {
+        DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => {
+            let data = out.as_mut_slice_with_len::<u8>(keys.len());
+            for (row, key) in keys.iter().enumerate() {
+                data[row] = positions[*key] as u8;
+            }
+        }
+        DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => {
+            let data = out.as_mut_slice_with_len::<u16>(keys.len());
+            for (row, key) in keys.iter().enumerate() {
+                data[row] = positions[*key] as u16;
+            }
+        }
+        _ => {
+            let data = out.as_mut_slice_with_len::<u32>(keys.len());
+            for (row, key) in keys.iter().enumerate() {
+                data[row] = positions[*key];
+            }
+        }
+    }
+    set_nulls_in_flat_vector(array, out);
+    Ok(())
+}
+
 /// Convert Arrow [BooleanArray] to a duckdb vector.
 fn boolean_array_to_vector(array: &BooleanArray, out: &mut FlatVector) {
     assert!(array.len() <= out.capacity());
@@ -1005,7 +1637,35 @@ fn list_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
     array: &GenericListArray<O>,
     out: &mut ListVector,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let value_array = array.values();
+    list_values_to_vector(array.values(), out)?;
+
+    for i in 0..array.len() {
+        let offset = array.value_offsets()[i];
+        let length = array.value_length(i);
+        out.set_entry(i, offset.as_(), length.as_());
+    }
+    set_nulls_in_list_vector(array, out);
+
+    Ok(())
+}
+
+/// Convert Arrow list view array to a duckdb list vector, duckdb list entries are views already.
+fn list_view_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
+    array: &GenericListViewArray<O>,
+    out: &mut ListVector,
+) -> Result<(), Box<dyn std::error::Error>> {
+    list_values_to_vector(array.values(), out)?;
+
+    for i in 0..array.len() {
+        out.set_entry(i, array.value_offsets()[i].as_(), array.value_sizes()[i].as_());
+    }
+    set_nulls_in_list_vector(array, out);
+
+    Ok(())
+}
+
+/// Write the child values of a list array to the child of a duckdb list vector.
+fn list_values_to_vector(value_array: &ArrayRef, out: &mut ListVector) -> Result<(), Box<dyn std::error::Error>> {
     match value_array.data_type() {
         dt if dt.is_primitive() || matches!(dt, DataType::Boolean) => {
             primitive_array_to_vector(value_array.as_ref(), &mut out.child(value_array.len()))?;
@@ -1051,6 +1711,18 @@ fn list_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
                 &mut out.struct_child(value_array.len()),
             )?;
         }
+        DataType::Dictionary(_, _) => {
+            dictionary_array_to_vector(value_array.as_ref(), &mut out.child(value_array.len()))?;
+        }
+        DataType::Map(_, _) => {
+            map_array_to_vector(
+                as_map_array(value_array.as_ref()),
+                &mut out.map_child(value_array.len()),
+            )?;
+        }
+        DataType::Union(_, _) => {
+            union_array_to_vector(value_array.as_union(), &mut out.union_child(value_array.len()))?;
+        }
         _ => {
             return Err(format!(
                 "List with elements of type '{}' are not currently supported.",
@@ -1060,12 +1732,7 @@ fn list_array_to_vector<O: OffsetSizeTrait + AsPrimitive<usize>>(
         }
     }
 
-    for i in 0..array.len() {
-        let offset = array.value_offsets()[i];
-        let length = array.value_length(i);
-        out.set_entry(i, offset.as_(), length.as_());
-    }
-    set_nulls_in_list_vector(array, out);
+    out.set_len(value_array.len());
 
     Ok(())
 }
@@ -1132,6 +1799,15 @@ fn struct_array_to_vector(array: &StructArray, out: &mut StructVector) -> Result
                 let mut struct_vector = out.struct_vector_child(i);
                 struct_array_to_vector(struct_array, &mut struct_vector)?;
             }
+            DataType::Dictionary(_, _) => {
+                dictionary_array_to_vector(column.as_ref(), &mut out.child(i, array.len()))?;
+            }
+            DataType::Map(_, _) => {
+                map_array_to_vector(as_map_array(column.as_ref()), &mut out.map_vector_child(i))?;
+            }
+            DataType::Union(_, _) => {
+                union_array_to_vector(column.as_union(), &mut out.union_vector_child(i))?;
+            }
             _ => {
                 unimplemented!(
                     "Unsupported data type: {}, please file an issue https://github.com/wangfenjin/duckdb-rs",
@@ -1144,6 +1820,108 @@ fn struct_array_to_vector(array: &StructArray, out: &mut StructVector) -> Result
     Ok(())
 }
 
+/// Convert Arrow [MapArray] to duckdb map vector.
+///
+/// A [MapArray] is physically a list of key value pairs stored as an `entries` [StructArray] with 2 child fields, just
+/// like a duckdb map.
+fn map_array_to_vector(array: &MapArray, out: &mut MapVector) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = array.entries();
+    struct_array_to_vector(entries, &mut out.entries(entries.len()))?;
+
+    for i in 0..array.len() {
+        let offset = array.value_offsets()[i];
+        let length = array.value_length(i);
+        out.set_entry(i, offset.as_(), length.as_());
+    }
+    out.set_len(entries.len());
+    set_nulls_in_map_vector(array, out);
+
+    Ok(())
+}
+
+/// Convert Arrow [UnionArray] to duckdb union vector.
+///
+/// Every row of a duckdb union has the tag of its member and a value in that member only, the other members are null.
+/// The values of a member are gathered from the Arrow child into the rows that selected it, which works for both
+/// sparse and dense unions as [UnionArray::value_offset] is the row itself for the former.
+fn union_array_to_vector(array: &UnionArray, out: &mut UnionVector) -> Result<(), Box<dyn std::error::Error>> {
+    let DataType::Union(fields, _) = array.data_type() else {
+        unreachable!()
+    };
+    let mut tags = out.tags(array.len());
+    let tags = tags.as_mut_slice::<u8>();
+
+    for (member, (type_id, _)) in fields.iter().enumerate() {
+        let indices: UInt32Array = (0..array.len())
+            .map(|row| {
+                if array.type_id(row) != type_id {
+                    return None;
+                }
+                tags[row] = member as u8;
+                Some(array.value_offset(row) as u32)
+            })
+            .collect();
+        let values = take(array.child(type_id).as_ref(), &indices, None)?;
+        union_member_to_vector(&values, out, member)?;
+    }
+
+    Ok(())
+}
+
+/// Write the values of a union member, already aligned to the rows of the union.
+fn union_member_to_vector(
+    values: &ArrayRef,
+    out: &mut UnionVector,
+    member: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match values.data_type() {
+        dt if dt.is_primitive() || matches!(dt, DataType::Boolean) => {
+            primitive_array_to_vector(values.as_ref(), &mut out.member(member, values.len()))?;
+        }
+        DataType::Utf8 => {
+            string_array_to_vector(as_string_array(values.as_ref()), &mut out.member(member, values.len()));
+        }
+        DataType::LargeUtf8 => {
+            string_array_to_vector(values.as_string::<i64>(), &mut out.member(member, values.len()));
+        }
+        DataType::Binary => {
+            binary_array_to_vector(
+                as_generic_binary_array(values.as_ref()),
+                &mut out.member(member, values.len()),
+            );
+        }
+        DataType::List(_) => {
+            list_array_to_vector(as_list_array(values.as_ref()), &mut out.list_vector_member(member))?;
+        }
+        DataType::LargeList(_) => {
+            list_array_to_vector(
+                as_large_list_array(values.as_ref()),
+                &mut out.list_vector_member(member),
+            )?;
+        }
+        DataType::FixedSizeList(_, _) => {
+            fixed_size_list_array_to_vector(
+                as_fixed_size_list_array(values.as_ref()),
+                &mut out.array_vector_member(member),
+            )?;
+        }
+        DataType::Struct(_) => {
+            struct_array_to_vector(as_struct_array(values.as_ref()), &mut out.struct_vector_member(member))?;
+        }
+        DataType::Map(_, _) => {
+            map_array_to_vector(as_map_array(values.as_ref()), &mut out.map_vector_member(member))?;
+        }
+        DataType::Dictionary(_, _) => {
+            dictionary_array_to_vector(values.as_ref(), &mut out.member(member, values.len()))?;
+        }
+        dt => {
+            return Err(format!("Union with members of type '{dt}' are not currently supported.").into());
+        }
+    }
+
+    Ok(())
+}
+
 /// Pass RecordBatch to duckdb.
 ///
 /// # Safety
@@ -1217,28 +1995,50 @@ fn set_nulls_in_list_vector(array: &dyn Array, out_vector: &mut ListVector) {
     }
 }
 
+fn set_nulls_in_map_vector(array: &dyn Array, out_vector: &mut MapVector) {
+    if let Some(nulls) = array.nulls() {
+        for (i, null) in nulls.into_iter().enumerate() {
+            if !null {
+                out_vector.set_null(i);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{arrow_recordbatch_to_query_params, ArrowVTab};
-    use crate::{Connection, Result};
+    use super::{arrow_recordbatch_to_query_params, data_chunk_to_arrow, ArrowVTab, VArrowTab};
+    use crate::{
+        core::{DataChunkHandle, LogicalType, LogicalTypeHandle, LogicalTypeId},
+        vtab::{BindInfo, InitInfo},
+        Connection, Result,
+    };
     use arrow::{
         array::{
             Array, ArrayRef, AsArray, BinaryArray, BinaryViewArray, BooleanArray, Date32Array, Date64Array,
-            Decimal128Array, Decimal256Array, DurationSecondArray, FixedSizeListArray, FixedSizeListBuilder,
-            GenericByteArray, GenericListArray, Int32Array, Int32Builder, IntervalDayTimeArray,
-            IntervalMonthDayNanoArray, IntervalYearMonthArray, LargeStringArray, ListArray, ListBuilder, MapArray,
-            OffsetSizeTrait, PrimitiveArray, StringArray, StringViewArray, StructArray, Time32SecondArray,
-            Time64MicrosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
-            TimestampSecondArray, UInt32Array,
+            Decimal128Array, Decimal256Array, DictionaryArray, DurationSecondArray, FixedSizeListArray,
+            FixedSizeListBuilder, Float32Array, GenericByteArray, GenericListArray, Int32Array, Int32Builder,
+            Int8Array, IntervalDayTimeArray, IntervalMonthDayNanoArray, IntervalYearMonthArray, LargeStringArray,
+            ListArray, ListBuilder, ListViewArray, MapArray, MapBuilder, OffsetSizeTrait, PrimitiveArray, RunArray,
+            StringArray, StringBuilder, StringViewArray, StructArray, Time32SecondArray, Time64MicrosecondArray,
+            Time64NanosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+            TimestampSecondArray, UInt32Array, UnionArray,
         },
         buffer::{OffsetBuffer, ScalarBuffer},
         datatypes::{
-            i256, ArrowPrimitiveType, ByteArrayType, DataType, DurationSecondType, Field, Fields, IntervalDayTimeType,
-            IntervalMonthDayNanoType, IntervalYearMonthType, Schema,
+            i256, ArrowPrimitiveType, ByteArrayType, DataType, DurationSecondType, Field, Fields, Float16Type,
+            Int32Type, Int8Type, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalYearMonthType, Schema,
+            SchemaRef, UnionFields,
         },
         record_batch::RecordBatch,
     };
-    use std::{error::Error, sync::Arc};
+    use std::{
+        error::Error,
+        sync::{
+            atomic::{AtomicI32, Ordering},
+            Arc,
+        },
+    };
 
     #[test]
     fn test_vtab_arrow() -> Result<(), Box<dyn Error>> {
@@ -1260,6 +2060,71 @@ mod test {
         Ok(())
     }
 
+    struct CountBatches;
+
+    impl VArrowTab for CountBatches {
+        type BindData = (SchemaRef, i32);
+        type InitData = AtomicI32;
+
+        fn bind(bind: &BindInfo) -> Result<(SchemaRef, Self::BindData), Box<dyn Error>> {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("i", DataType::Int32, false),
+                Field::new("s", DataType::Utf8, true),
+            ]));
+            let batches = bind.get_parameter(0).to_int64() as i32;
+            Ok((schema.clone(), (schema, batches)))
+        }
+
+        fn init(_: &InitInfo) -> Result<Self::InitData, Box<dyn Error>> {
+            Ok(AtomicI32::new(0))
+        }
+
+        fn next_batch(
+            (schema, batches): &Self::BindData,
+            produced: &Self::InitData,
+        ) -> Result<Option<RecordBatch>, Box<dyn Error>> {
+            let batch = produced.fetch_add(1, Ordering::Relaxed);
+            if batch >= *batches {
+                return Ok(None);
+            }
+            // larger than a single DuckDB vector, so every batch spans multiple chunks
+            let values = (0..3000).map(|i| batch * 3000 + i);
+            let strings = values.clone().map(|i| (i % 2 == 0).then(|| i.to_string()));
+            Ok(Some(RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from_iter_values(values)),
+                    Arc::new(StringArray::from_iter(strings)),
+                ],
+            )?))
+        }
+
+        fn parameters() -> Option<Vec<LogicalTypeHandle>> {
+            Some(vec![LogicalTypeHandle::from(LogicalTypeId::Integer)])
+        }
+    }
+
+    #[test]
+    fn test_varrowtab() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<CountBatches>("count_batches")?;
+
+        let (count, sum, strings): (i64, i64, i64) =
+            db.query_row("select count(*), sum(i), count(s) from count_batches(3)", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?;
+        assert_eq!(count, 9000);
+        assert_eq!(sum, (0..9000).sum::<i64>());
+        assert_eq!(strings, 4500);
+
+        let s: String = db.query_row("select s from count_batches(2) where i = 4242", [], |row| row.get(0))?;
+        assert_eq!(s, "4242");
+
+        let count: i64 = db.query_row("select count(*) from count_batches(0)", [], |row| row.get(0))?;
+        assert_eq!(count, 0);
+        Ok(())
+    }
+
     #[test]
     fn test_vtab_arrow_rust_array() -> Result<(), Box<dyn Error>> {
         let db = Connection::open_in_memory()?;
@@ -1482,6 +2347,82 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_data_chunk_to_arrow_unordered_list() -> Result<(), Box<dyn Error>> {
+        let chunk = DataChunkHandle::new(&[LogicalTypeHandle::list(&LogicalTypeId::Integer.into())]);
+        let mut list = chunk.list_vector(0);
+        list.set_child(&[1, 2, 3, 4]);
+        list.set_entry(0, 2, 2);
+        list.set_null(1);
+        list.set_entry(2, 0, 2);
+        chunk.set_len(3);
+
+        let batch = data_chunk_to_arrow(&chunk)?;
+        let expected = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(3), Some(4)]),
+            None,
+            Some(vec![Some(1), Some(2)]),
+        ]);
+        assert_eq!(batch.column(0).as_list::<i32>().values(), expected.values());
+        assert_eq!(batch.column(0).logical_nulls(), expected.logical_nulls());
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_chunk_to_arrow_enum() -> Result<(), Box<dyn Error>> {
+        let chunk = DataChunkHandle::new(&[LogicalTypeHandle::enum_type(&["sad", "ok", "happy"])?]);
+        let mut vector = chunk.flat_vector(0);
+        vector.copy(&[2u8, 0, 0]);
+        vector.set_null(1);
+        chunk.set_len(3);
+
+        let batch = data_chunk_to_arrow(&chunk)?;
+        let column = batch.column(0).as_dictionary::<arrow::datatypes::UInt32Type>();
+        let values = column
+            .downcast_dict::<StringArray>()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![Some("happy"), None, Some("sad")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_chunk_to_arrow_array() -> Result<(), Box<dyn Error>> {
+        let element = LogicalTypeHandle::from(LogicalTypeId::Integer);
+        let chunk = DataChunkHandle::new(&[LogicalTypeHandle::array(&element, 2)]);
+        let mut vector = chunk.flat_vector(0);
+        let array_vector = chunk.array_vector(0);
+        array_vector.set_child(&[1i32, 2, 0, 0, 5, 6]);
+        vector.set_null(1);
+        chunk.set_len(3);
+
+        let batch = data_chunk_to_arrow(&chunk)?;
+        let column = batch.column(0).as_fixed_size_list();
+        assert_eq!(column.value_length(), 2);
+        assert!(column.is_null(1));
+        assert_eq!(column.value(2).as_primitive::<Int32Type>().values(), &[5, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_logical_type_from_arrow() -> Result<(), Box<dyn Error>> {
+        let data_type = DataType::FixedSizeList(
+            Arc::new(Field::new(
+                "item",
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+                true,
+            )),
+            3,
+        );
+        assert_eq!(
+            LogicalType::try_from(&data_type)?,
+            LogicalType::Array(Box::new(LogicalType::Varchar), 3)
+        );
+        assert!(LogicalType::try_from(&DataType::Decimal128(10, -2)).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_array_roundtrip() -> Result<(), Box<dyn Error>> {
         check_generic_array_roundtrip(ListArray::new(
@@ -1695,6 +2636,111 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_decimal256_roundtrip() -> Result<(), Box<dyn Error>> {
+        let array =
+            Decimal256Array::from(vec![i256::from(12345), i256::from(-1)]).with_data_type(DataType::Decimal256(38, 2));
+        let expected =
+            Decimal128Array::from(vec![i128::from(12345), i128::from(-1)]).with_data_type(DataType::Decimal128(38, 2));
+        check_rust_primitive_array_roundtrip(array, expected)?;
+
+        let array = Decimal256Array::from(vec![i256::from(123456)]).with_data_type(DataType::Decimal256(5, 0));
+        assert!(arrow_vtab_error(Arc::new(array)).contains("too large to store in a Decimal128 of precision 5"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_float16_roundtrip() -> Result<(), Box<dyn Error>> {
+        let expected = Float32Array::from(vec![Some(1.5), None, Some(65504.0)]);
+        let array = arrow::compute::cast(&expected, &DataType::Float16)?;
+        check_rust_primitive_array_roundtrip(array.as_primitive::<Float16Type>().clone(), expected)
+    }
+
+    #[test]
+    fn test_temporal_range_checks() {
+        let mid = arrow::temporal_conversions::MILLISECONDS_IN_DAY;
+        assert!(arrow_vtab_error(Arc::new(Date64Array::from(vec![mid + 1]))).contains("not a whole number of days"));
+        assert!(
+            arrow_vtab_error(Arc::new(Date64Array::from(vec![i64::MAX / mid * mid]))).contains("out of range for DATE")
+        );
+        assert!(arrow_vtab_error(Arc::new(Time32SecondArray::from(vec![90_000]))).contains("out of range for TIME"));
+        assert!(arrow_vtab_error(Arc::new(Time64NanosecondArray::from(vec![1_500]))).contains("would lose precision"));
+    }
+
+    #[test]
+    fn test_list_view_roundtrip() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t (a INTEGER[])")?;
+
+        // views may overlap and be out of order
+        let array = ListViewArray::new(
+            Arc::new(Field::new("item", DataType::Int32, true)),
+            ScalarBuffer::from(vec![2, 0, 0]),
+            ScalarBuffer::from(vec![2, 3, 0]),
+            Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+            Some(vec![true, true, false].into()),
+        );
+        let rb = RecordBatch::try_from_iter(vec![("a", Arc::new(array) as ArrayRef)])?;
+
+        // the Arrow C data interface of arrow-rs can't export list views yet, so write them with the appender
+        db.appender("t")?.append_record_batch(rb)?;
+        let mut stmt = db.prepare("select a from t")?;
+        let rb = stmt.query_arrow([])?.next().expect("no record batch");
+        let output = rb.column(0).as_list::<i32>();
+        assert_eq!(
+            output.value(0).as_primitive::<Int32Type>(),
+            &Int32Array::from(vec![3, 4])
+        );
+        assert_eq!(
+            output.value(1).as_primitive::<Int32Type>(),
+            &Int32Array::from(vec![1, 2, 3])
+        );
+        assert!(output.is_null(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_end_encoded_roundtrip() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        let array = RunArray::<Int32Type>::try_new(
+            &Int32Array::from(vec![2, 5, 6]),
+            &StringArray::from(vec![Some("a"), Some("b"), None]),
+        )?;
+        for (array, expected) in [
+            (
+                Arc::new(array.clone()) as ArrayRef,
+                vec![Some("a"), Some("a"), Some("b"), Some("b"), Some("b"), None],
+            ),
+            (
+                Arc::new(array.slice(1, 3)) as ArrayRef,
+                vec![Some("a"), Some("b"), Some("b")],
+            ),
+        ] {
+            let rb = RecordBatch::try_from_iter(vec![("a", array)])?;
+            let param = arrow_recordbatch_to_query_params(rb);
+            let mut stmt = db.prepare("select a from arrow(?, ?)")?;
+            let rb = stmt.query_arrow(param)?.next().expect("no record batch");
+            assert_eq!(rb.column(0).as_string::<i32>(), &StringArray::from(expected));
+        }
+
+        Ok(())
+    }
+
+    fn arrow_vtab_error(array: ArrayRef) -> String {
+        let db = Connection::open_in_memory().unwrap();
+        db.register_table_function::<ArrowVTab>("arrow").unwrap();
+
+        let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
+        let mut stmt = db.prepare("select a from arrow(?, ?)").unwrap();
+        stmt.execute(arrow_recordbatch_to_query_params(batch))
+            .unwrap_err()
+            .to_string()
+    }
+
     #[test]
     fn test_interval_roundtrip() -> Result<(), Box<dyn Error>> {
         let array: PrimitiveArray<IntervalMonthDayNanoType> = IntervalMonthDayNanoArray::from(vec![
@@ -1785,7 +2831,11 @@ mod test {
                     code: crate::ffi::ErrorCode::Unknown,
                     extended_code: 1
                 },
-                Some("Invalid Input Error: Data type \"Decimal256(76, 10)\" not yet supported by ArrowVTab".to_owned())
+                Some(
+                    "Binder Error: Unsupported data type: Decimal256(76, 10), precision 76 would be lost as DECIMAL \
+                     supports at most 38 digits"
+                        .to_owned()
+                )
             )
         );
     }
@@ -2073,4 +3123,240 @@ mod test {
 
         Ok(())
     }
+
+    fn arrow_vtab_strings(array: ArrayRef, sql: &str) -> Result<Vec<Vec<Option<String>>>, Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        let rb = RecordBatch::try_from_iter(vec![("a", array)])?;
+        let param = arrow_recordbatch_to_query_params(rb);
+        let mut stmt = db.prepare(sql)?;
+        let rows = stmt
+            .query_map(param, |row| {
+                (0..row.as_ref().column_count())
+                    .map(|i| row.get::<_, Option<String>>(i))
+                    .collect()
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    #[test]
+    fn test_union_roundtrip() -> Result<(), Box<dyn Error>> {
+        let fields = UnionFields::new(
+            vec![3, 1],
+            vec![
+                Field::new("int", DataType::Int32, true),
+                Field::new("str", DataType::Utf8, true),
+            ],
+        );
+        let sql = "select union_tag(a)::varchar, a::varchar from arrow(?, ?)";
+        let tagged = |rows: &[(&str, Option<&str>)]| -> Vec<Vec<Option<String>>> {
+            rows.iter()
+                .map(|(tag, value)| vec![Some(tag.to_string()), value.map(String::from)])
+                .collect()
+        };
+
+        let sparse = UnionArray::try_new(
+            fields.clone(),
+            vec![3, 1, 1, 3].into(),
+            None,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, None, None])),
+                Arc::new(StringArray::from(vec![None, Some("a"), Some("b"), None])),
+            ],
+        )?;
+        // a row with a NULL member is a valid union, printed as the NULL member
+        assert_eq!(
+            arrow_vtab_strings(Arc::new(sparse), sql)?,
+            tagged(&[
+                ("int", Some("1")),
+                ("str", Some("a")),
+                ("str", Some("b")),
+                ("int", Some("NULL"))
+            ])
+        );
+
+        let dense = UnionArray::try_new(
+            fields,
+            vec![1, 3, 1, 3].into(),
+            Some(vec![0, 0, 1, 1].into()),
+            vec![
+                Arc::new(Int32Array::from(vec![7, 8])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+            ],
+        )?;
+        assert_eq!(
+            arrow_vtab_strings(Arc::new(dense.clone()), sql)?,
+            tagged(&[
+                ("str", Some("x")),
+                ("int", Some("7")),
+                ("str", Some("y")),
+                ("int", Some("8"))
+            ])
+        );
+
+        // unions nested in lists
+        let list = ListArray::new(
+            Arc::new(Field::new("item", dense.data_type().clone(), false)),
+            OffsetBuffer::new(vec![0, 3, 4].into()),
+            Arc::new(dense),
+            None,
+        );
+        assert_eq!(
+            arrow_vtab_strings(Arc::new(list), "select a::varchar from arrow(?, ?)")?,
+            vec![vec![Some("[x, 7, y]".to_string())], vec![Some("[8]".to_string())]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_map_roundtrip() -> Result<(), Box<dyn Error>> {
+        // maps with list values, nested in a struct
+        let mut builder = MapBuilder::new(None, StringBuilder::new(), ListBuilder::new(Int32Builder::new()));
+        builder.keys().append_value("a");
+        builder.values().append_value([Some(1), Some(2)]);
+        builder.keys().append_value("b");
+        builder.values().append_value([]);
+        builder.append(true)?;
+        builder.append(false)?;
+        builder.keys().append_value("c");
+        builder.values().append_null();
+        builder.append(true)?;
+        let map = builder.finish();
+
+        let array = StructArray::try_from(vec![("m", Arc::new(map) as ArrayRef)])?;
+        assert_eq!(
+            arrow_vtab_strings(
+                Arc::new(array),
+                "select a.m::varchar, cardinality(a.m)::varchar from arrow(?, ?)"
+            )?,
+            vec![
+                vec![Some("{a=[1, 2], b=[]}".to_string()), Some("2".to_string())],
+                vec![None, None],
+                vec![Some("{c=NULL}".to_string()), Some("1".to_string())],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_of_many_maps() -> Result<(), Box<dyn Error>> {
+        // more maps in one list than the default capacity of the list child vector
+        let mut builder = ListBuilder::new(MapBuilder::new(None, Int32Builder::new(), Int32Builder::new()));
+        for i in 0..3000 {
+            builder.values().keys().append_value(i);
+            builder.values().values().append_value(i * 2);
+            builder.values().append(true)?;
+        }
+        builder.append(true);
+        let list = builder.finish();
+
+        assert_eq!(
+            arrow_vtab_strings(
+                Arc::new(list),
+                "select len(a)::varchar, a[3000][2999]::varchar from arrow(?, ?)"
+            )?,
+            vec![vec![Some("3000".to_string()), Some("5998".to_string())]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_enum_roundtrip() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        let array: DictionaryArray<Int8Type> = vec![Some("b"), Some("a"), None, Some("b")].into_iter().collect();
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array.clone())])?;
+
+        let param = arrow_recordbatch_to_query_params(rb);
+        let mut stmt = db.prepare("select a, a::varchar from arrow(?, ?)")?;
+        let rb = stmt.query_arrow(param)?.next().expect("no record batch");
+
+        // ENUM columns are returned as dictionaries of their members
+        let output = rb.column(0).as_any_dictionary();
+        assert_eq!(output.values().as_string::<i32>(), array.values().as_string::<i32>());
+        assert_eq!(output.keys().null_count(), 1);
+        let strings = rb.column(1).as_string::<i32>();
+        assert_eq!(strings, &StringArray::from(vec![Some("b"), Some("a"), None, Some("b")]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_without_values() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        let keys = Int8Array::from(vec![None, None, None]);
+        let array = DictionaryArray::<Int8Type>::try_new(keys, Arc::new(StringArray::from(Vec::<&str>::new())))?;
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)])?;
+
+        // an ENUM needs members, so the column is a VARCHAR
+        let param = arrow_recordbatch_to_query_params(rb);
+        let (count, nulls, type_name): (i64, i64, String) = db.query_row(
+            "select count(*), count(*) - count(a), any_value(typeof(a)) from arrow(?, ?)",
+            param,
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!((count, nulls, type_name.as_str()), (3, 3, "VARCHAR"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_expanded() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        // non-string values can't be enum members and are expanded
+        let array = DictionaryArray::<Int32Type>::try_new(
+            Int32Array::from(vec![Some(1), None, Some(0), Some(1)]),
+            Arc::new(Int32Array::from(vec![10, 20])),
+        )?;
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)])?;
+
+        let param = arrow_recordbatch_to_query_params(rb);
+        let mut stmt = db.prepare("select a from arrow(?, ?)")?;
+        let rb = stmt.query_arrow(param)?.next().expect("no record batch");
+        assert_eq!(
+            rb.column(0).as_primitive::<Int32Type>(),
+            &Int32Array::from(vec![Some(20), None, Some(10), Some(20)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_of_dictionaries_roundtrip() -> Result<(), Box<dyn Error>> {
+        let db = Connection::open_in_memory()?;
+        db.register_table_function::<ArrowVTab>("arrow")?;
+
+        let values: DictionaryArray<Int32Type> = vec!["x", "y", "x", "z", "y"].into_iter().collect();
+        let list = ListArray::new(
+            Arc::new(Field::new("item", values.data_type().clone(), true)),
+            OffsetBuffer::new(ScalarBuffer::from(vec![0, 2, 5])),
+            Arc::new(values),
+            None,
+        );
+        let schema = Schema::new(vec![Field::new("a", list.data_type().clone(), true)]);
+        let rb = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list)])?;
+
+        let param = arrow_recordbatch_to_query_params(rb);
+        let mut stmt = db.prepare("select a::varchar[] from arrow(?, ?)")?;
+        let rb = stmt.query_arrow(param)?.next().expect("no record batch");
+        let output = rb.column(0).as_list::<i32>();
+        assert_eq!(
+            output.value(1).as_string::<i32>(),
+            &StringArray::from(vec!["x", "z", "y"])
+        );
+
+        Ok(())
+    }
 }
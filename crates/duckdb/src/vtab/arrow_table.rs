@@ -0,0 +1,405 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    ffi::c_void,
+    sync::{
+        atomic::{self, AtomicUsize},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+};
+
+use arrow::{
+    datatypes::SchemaRef,
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+
+use super::{arrow::ArrowTabBindData, drop_boxed, table_function, BindInfo, InitInfo, VArrowTab};
+use crate::{
+    core::{LogicalTypeHandle, LogicalTypeId},
+    ffi,
+    pragma::Sql,
+    Connection, Error, Result,
+};
+
+/// Where the record batches of a registered table come from.
+enum ArrowSource {
+    /// Batches kept in memory, every scan reads all of them.
+    Batches(Arc<[RecordBatch]>),
+    /// A reader that is handed to the first scan, `None` once consumed.
+    Reader(Option<Box<dyn RecordBatchReader + Send>>),
+}
+
+struct ArrowTable {
+    schema: SchemaRef,
+    source: ArrowSource,
+}
+
+type SharedArrowTable = Arc<Mutex<ArrowTable>>;
+
+/// The registered tables of a connection by name, also owned by the scan function of the connection.
+#[derive(Default)]
+struct TableMap(Mutex<HashMap<String, SharedArrowTable>>);
+
+impl TableMap {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, SharedArrowTable>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The Arrow tables registered on a connection.
+#[derive(Default)]
+pub(crate) struct ArrowTables {
+    tables: Arc<TableMap>,
+    /// Whether the scan function of the connection is registered in the database.
+    scan_function: Cell<bool>,
+}
+
+impl ArrowTables {
+    /// The name of the scan function of the connection.
+    ///
+    /// The scan function keeps the table map alive, so no other connection gets a map at the same address while
+    /// the function exists.
+    fn scan_function(&self) -> String {
+        format!("__duckdb_rs_arrow_scan_{:x}", Arc::as_ptr(&self.tables) as usize)
+    }
+}
+
+impl Drop for ArrowTables {
+    /// Release the data of the tables, the scan function outlives the connection but keeps an empty map.
+    fn drop(&mut self) {
+        self.tables.lock().clear();
+    }
+}
+
+/// The state of a single scan of a registered table.
+enum ArrowScan {
+    Batches {
+        batches: Arc<[RecordBatch]>,
+        next: AtomicUsize,
+    },
+    Reader(Mutex<Box<dyn RecordBatchReader + Send>>),
+}
+
+/// The table function behind the registered Arrow tables of a connection, its parameter is the name of the table.
+struct RegisteredArrowVTab;
+
+impl VArrowTab for RegisteredArrowVTab {
+    type BindData = SharedArrowTable;
+    type InitData = ArrowScan;
+
+    fn bind(bind: &BindInfo) -> Result<(SchemaRef, Self::BindData), Box<dyn std::error::Error>> {
+        // Safety: the extra info is set to the `Arc<TableMap>` of the connection by `register_arrow_scan`
+        let tables = unsafe { &*bind.get_extra_info::<Arc<TableMap>>() };
+        let name = bind.get_parameter(0).to_string();
+        let table = tables
+            .lock()
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| format!("no Arrow table registered as \"{name}\""))?;
+        let schema = table.lock().unwrap_or_else(PoisonError::into_inner).schema.clone();
+        Ok((schema, table))
+    }
+
+    fn init(init: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        // Safety: the bind data of a `VArrowTab` is its `ArrowTabBindData`
+        let bind_data = unsafe { &*init.get_bind_data::<ArrowTabBindData<Self>>() };
+        let mut table = bind_data.inner().lock().unwrap_or_else(PoisonError::into_inner);
+        match &mut table.source {
+            ArrowSource::Batches(batches) => Ok(ArrowScan::Batches {
+                batches: batches.clone(),
+                next: AtomicUsize::new(0),
+            }),
+            ArrowSource::Reader(reader) => match reader.take() {
+                Some(reader) => Ok(ArrowScan::Reader(Mutex::new(reader))),
+                None => Err("the record batch reader was already consumed".into()),
+            },
+        }
+    }
+
+    fn next_batch(
+        _: &Self::BindData,
+        scan: &Self::InitData,
+    ) -> Result<Option<RecordBatch>, Box<dyn std::error::Error>> {
+        match scan {
+            ArrowScan::Batches { batches, next } => {
+                Ok(batches.get(next.fetch_add(1, atomic::Ordering::Relaxed)).cloned())
+            }
+            ArrowScan::Reader(reader) => Ok(reader
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .next()
+                .transpose()?),
+        }
+    }
+
+    fn parameters() -> Option<Vec<LogicalTypeHandle>> {
+        Some(vec![LogicalTypeHandle::from(LogicalTypeId::Varchar)])
+    }
+}
+
+fn failure(message: String) -> Error {
+    Error::DuckDBFailure(ffi::Error::new(ffi::DuckDBError), Some(message))
+}
+
+impl Connection {
+    /// Register record batches as a temporary view named `name`.
+    ///
+    /// The batches are kept in memory until the view is unregistered or the connection is dropped, and can be
+    /// queried any number of times. All batches must share the schema of the first one.
+    ///
+    /// The view is temporary, so only this connection can query it. The data is read through a table function that
+    /// is registered in the database for every connection registering a table, and only reads the tables of its
+    /// connection.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{arrow::record_batch::RecordBatch, Connection, Result};
+    /// fn count_rows(conn: &Connection, batches: Vec<RecordBatch>) -> Result<i64> {
+    ///     conn.register_record_batches("batches", batches)?;
+    ///     let count = conn.query_row("SELECT count(*) FROM batches", [], |row| row.get(0))?;
+    ///     conn.unregister("batches")?;
+    ///     Ok(count)
+    /// }
+    /// ```
+    pub fn register_record_batches(&self, name: &str, batches: Vec<RecordBatch>) -> Result<()> {
+        let schema = match batches.first() {
+            Some(batch) => batch.schema(),
+            None => return Err(failure(format!("no record batches to register as \"{name}\""))),
+        };
+        if let Some(batch) = batches.iter().find(|batch| batch.schema() != schema) {
+            return Err(failure(format!(
+                "record batches of \"{name}\" have different schemas: {} and {}",
+                schema,
+                batch.schema()
+            )));
+        }
+        self.register_arrow_source(
+            name,
+            ArrowTable {
+                schema,
+                source: ArrowSource::Batches(batches.into()),
+            },
+        )
+    }
+
+    /// Register a record batch reader as a temporary view named `name`.
+    ///
+    /// The batches are streamed from the reader while the view is scanned, so the view can only be queried once;
+    /// later scans fail. The reader is dropped when the view is unregistered or the connection is dropped. See
+    /// [`Connection::register_record_batches`] for the visibility of the view.
+    pub fn register_record_batch_reader<R>(&self, name: &str, reader: R) -> Result<()>
+    where
+        R: RecordBatchReader + Send + 'static,
+    {
+        self.register_arrow_source(
+            name,
+            ArrowTable {
+                schema: reader.schema(),
+                source: ArrowSource::Reader(Some(Box::new(reader))),
+            },
+        )
+    }
+
+    /// Unregister a table registered by [`Connection::register_record_batches`] or
+    /// [`Connection::register_record_batch_reader`], dropping the view and releasing its data.
+    pub fn unregister(&self, name: &str) -> Result<()> {
+        if self.arrow_tables.tables.lock().remove(name).is_none() {
+            return Err(failure(format!("no Arrow table registered as \"{name}\"")));
+        }
+        let mut view = Sql::new();
+        view.push_identifier(name);
+        self.execute_batch(&format!("DROP VIEW IF EXISTS temp.main.{}", view.as_str()))
+    }
+
+    fn register_arrow_source(&self, name: &str, table: ArrowTable) -> Result<()> {
+        self.register_arrow_scan()?;
+        // the view is bound when it is created, so the table must be registered first
+        let previous = self
+            .arrow_tables
+            .tables
+            .lock()
+            .insert(name.to_owned(), Arc::new(Mutex::new(table)));
+
+        let mut view = Sql::new();
+        view.push_identifier(name);
+        let mut table_name = Sql::new();
+        table_name.push_string_literal(name);
+        let created = self.execute_batch(&format!(
+            "CREATE OR REPLACE TEMP VIEW {} AS SELECT * FROM {}({})",
+            view.as_str(),
+            self.arrow_tables.scan_function(),
+            table_name.as_str()
+        ));
+
+        // replacing a table releases the data of the previous one, unless the view could not be replaced
+        if created.is_err() {
+            let mut tables = self.arrow_tables.tables.lock();
+            match previous {
+                Some(previous) => tables.insert(name.to_owned(), previous),
+                None => tables.remove(name),
+            };
+        }
+        created
+    }
+
+    /// Register the scan function of the connection, whose extra info owns the table map.
+    fn register_arrow_scan(&self) -> Result<()> {
+        if self.arrow_tables.scan_function.get() {
+            return Ok(());
+        }
+        let table_function = table_function::<RegisteredArrowVTab>(&self.arrow_tables.scan_function());
+        let tables: Box<Arc<TableMap>> = Box::new(self.arrow_tables.tables.clone());
+        unsafe {
+            table_function.set_extra_info(Box::into_raw(tables) as *mut c_void, Some(drop_boxed::<Arc<TableMap>>));
+        }
+        self.db.borrow_mut().register_table_function(table_function)?;
+        self.arrow_tables.scan_function.set(true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{error::Error, sync::Arc};
+
+    use arrow::{
+        array::{ArrayRef, Int32Array, Int64Array, StringArray},
+        datatypes::{DataType, Field, Schema},
+        record_batch::{RecordBatch, RecordBatchIterator},
+    };
+
+    use crate::Connection;
+
+    fn batch(start: i32, len: i32) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let ids = Int32Array::from_iter_values(start..start + len);
+        let names = StringArray::from_iter((start..start + len).map(|i| (i % 3 != 0).then(|| format!("row {i}"))));
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(ids), Arc::new(names)]).unwrap()
+    }
+
+    #[test]
+    fn test_register_record_batches() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_record_batches("batches", vec![batch(0, 3000), batch(3000, 10)])?;
+
+        for _ in 0..2 {
+            let (count, names, max): (i64, i64, i32) =
+                conn.query_row("SELECT count(*), count(name), max(id) FROM batches", [], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                })?;
+            assert_eq!(count, 3010);
+            assert_eq!(names, 2006);
+            assert_eq!(max, 3009);
+        }
+
+        // registering again replaces the view
+        conn.register_record_batches("batches", vec![batch(0, 5)])?;
+        let count: i64 = conn.query_row("SELECT count(*) FROM batches", [], |row| row.get(0))?;
+        assert_eq!(count, 5);
+
+        conn.unregister("batches")?;
+        assert!(conn
+            .query_row("SELECT count(*) FROM batches", [], |row| row.get::<_, i64>(0))
+            .is_err());
+        assert!(conn.unregister("batches").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_record_batches_scope() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        let other = conn.try_clone()?;
+        conn.register_record_batches("a", vec![batch(0, 1)])?;
+        conn.register_record_batches("b", vec![batch(0, 2)])?;
+        other.register_record_batches("a", vec![batch(0, 3)])?;
+
+        let count = |conn: &Connection, name: &str| -> crate::Result<i64> {
+            conn.query_row(&format!("SELECT count(*) FROM {name}"), [], |row| row.get(0))
+        };
+        assert_eq!(count(&conn, "a")?, 1);
+        assert_eq!(count(&other, "a")?, 3);
+        assert!(count(&other, "b").is_err());
+
+        // every connection scans its own tables
+        let functions: Vec<String> = conn
+            .prepare("SELECT function_name FROM duckdb_functions() WHERE function_name LIKE '__duckdb_rs_arrow%'")?
+            .query_map([], |row| row.get(0))?
+            .collect::<crate::Result<_>>()?;
+        assert_eq!(functions.len(), 2);
+        let sql = format!("SELECT count(*) FROM {}('b')", other.arrow_tables.scan_function());
+        assert!(conn.query_row(&sql, [], |row| row.get::<_, i64>(0)).is_err());
+
+        // dropping the connection releases its tables
+        let ids: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        other.register_record_batches("c", vec![RecordBatch::try_from_iter(vec![("id", ids.clone())])?])?;
+        drop(other);
+        assert_eq!(Arc::strong_count(&ids), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_record_batches_view_error() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch("CREATE TEMP TABLE t (i INTEGER)")?;
+        let ids: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        let batch = RecordBatch::try_from_iter(vec![("id", ids.clone())])?;
+        assert!(conn.register_record_batches("t", vec![batch]).is_err());
+
+        // the failed registration released its batches
+        assert_eq!(Arc::strong_count(&ids), 1);
+        assert!(conn.unregister("t").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_record_batches_invalid() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        assert!(conn.register_record_batches("empty", vec![]).is_err());
+
+        let other = RecordBatch::try_from_iter(vec![("id", Arc::new(Int32Array::from(vec![1])) as _)])?;
+        assert!(conn.register_record_batches("mixed", vec![batch(0, 1), other]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_record_batch_reader() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        let batches = vec![batch(0, 100), batch(100, 100)];
+        let schema = batches[0].schema();
+        let reader = RecordBatchIterator::new(batches.into_iter().map(Ok), schema);
+        conn.register_record_batch_reader("stream", reader)?;
+
+        let sum: i64 = conn.query_row("SELECT sum(id) FROM stream", [], |row| row.get(0))?;
+        assert_eq!(sum, (0..200).sum::<i64>());
+
+        // the reader was consumed by the first scan
+        assert!(conn
+            .query_row("SELECT count(*) FROM stream", [], |row| row.get::<_, i64>(0))
+            .is_err());
+        conn.unregister("stream")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_record_batch_reader_wrong_type() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        let wrong = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1])) as _),
+            ("name", Arc::new(StringArray::from(vec!["a"])) as _),
+        ])?;
+        // the reader does not check its batches against its schema
+        let reader = RecordBatchIterator::new(vec![Ok(wrong)], batch(0, 1).schema());
+        conn.register_record_batch_reader("stream", reader)?;
+
+        let err = conn
+            .query_row("SELECT count(*) FROM stream", [], |row| row.get::<_, i64>(0))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("column \"id\" of the record batch is Int64"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
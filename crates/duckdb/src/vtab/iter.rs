@@ -0,0 +1,286 @@
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+};
+
+use super::{drop_boxed, table_function, BindInfo, InitInfo, TableFunctionInfo, VTab};
+use crate::{
+    core::{DataChunkHandle, FlatVector, Inserter, LogicalTypeHandle, LogicalTypeId},
+    ffi::duckdb_vector_size,
+    Connection, Result,
+};
+
+/// A Rust value that can be written into a column of a table function.
+pub trait TableColumn {
+    /// The DuckDB type of the column.
+    fn logical_type() -> LogicalTypeHandle;
+
+    /// Write the value into `vector` at `row`.
+    fn write_to(self, vector: &mut FlatVector, row: usize);
+}
+
+macro_rules! impl_table_column_primitive {
+    ($ty:ty, $type_id:ident) => {
+        impl TableColumn for $ty {
+            #[inline]
+            fn logical_type() -> LogicalTypeHandle {
+                LogicalTypeHandle::from(LogicalTypeId::$type_id)
+            }
+
+            #[inline]
+            fn write_to(self, vector: &mut FlatVector, row: usize) {
+                vector.as_mut_slice::<$ty>()[row] = self;
+            }
+        }
+    };
+}
+
+impl_table_column_primitive!(bool, Boolean);
+impl_table_column_primitive!(i8, Tinyint);
+impl_table_column_primitive!(i16, Smallint);
+impl_table_column_primitive!(i32, Integer);
+impl_table_column_primitive!(i64, Bigint);
+impl_table_column_primitive!(u8, UTinyint);
+impl_table_column_primitive!(u16, USmallint);
+impl_table_column_primitive!(u32, UInteger);
+impl_table_column_primitive!(u64, UBigint);
+impl_table_column_primitive!(f32, Float);
+impl_table_column_primitive!(f64, Double);
+
+impl TableColumn for String {
+    #[inline]
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeHandle::from(LogicalTypeId::Varchar)
+    }
+
+    #[inline]
+    fn write_to(self, vector: &mut FlatVector, row: usize) {
+        vector.insert(row, self.as_bytes());
+    }
+}
+
+impl TableColumn for Vec<u8> {
+    #[inline]
+    fn logical_type() -> LogicalTypeHandle {
+        LogicalTypeHandle::from(LogicalTypeId::Blob)
+    }
+
+    #[inline]
+    fn write_to(self, vector: &mut FlatVector, row: usize) {
+        vector.insert(row, self.as_slice());
+    }
+}
+
+impl<T: TableColumn> TableColumn for Option<T> {
+    #[inline]
+    fn logical_type() -> LogicalTypeHandle {
+        T::logical_type()
+    }
+
+    #[inline]
+    fn write_to(self, vector: &mut FlatVector, row: usize) {
+        match self {
+            Some(value) => value.write_to(vector, row),
+            None => vector.set_null(row),
+        }
+    }
+}
+
+/// A Rust struct that describes a row of a table function, see [`Connection::register_iter_table`].
+///
+/// With the `derive` feature this trait can be derived for structs with named fields whose types implement
+/// [`TableColumn`], every field becomes a column of the same name.
+pub trait TableRow {
+    /// The names and types of the columns, in order.
+    fn columns() -> Vec<(String, LogicalTypeHandle)>;
+
+    /// Write the row into `output` at `row`.
+    fn write_to(self, output: &mut DataChunkHandle, row: usize);
+}
+
+/// The factory of the iterator, stored as the extra info of the table function.
+type IterFactory<I> = Box<dyn Fn() -> I + Send + Sync>;
+
+/// A table function that produces the items of an iterator.
+struct IterVTab<T, I>(PhantomData<(T, I)>);
+
+impl<T, I> VTab for IterVTab<T, I>
+where
+    T: TableRow,
+    I: Iterator<Item = T> + Send,
+{
+    type InitData = Mutex<I>;
+    type BindData = ();
+
+    fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
+        for (name, logical_type) in T::columns() {
+            bind.add_result_column(&name, logical_type);
+        }
+        Ok(())
+    }
+
+    fn init(init: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        // Safety: the extra info is set to an `IterFactory<I>` by `register_iter_table`
+        let factory = unsafe { &*init.get_extra_info::<IterFactory<I>>() };
+        Ok(Mutex::new(factory()))
+    }
+
+    fn func(func: &TableFunctionInfo<Self>, output: &mut DataChunkHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let mut iter = func.get_init_data().lock().unwrap_or_else(PoisonError::into_inner);
+        let capacity = unsafe { duckdb_vector_size() } as usize;
+        let mut len = 0;
+        while len < capacity {
+            match iter.next() {
+                Some(item) => item.write_to(output, len),
+                None => break,
+            }
+            len += 1;
+        }
+        output.set_len(len);
+        Ok(())
+    }
+}
+
+impl Connection {
+    /// Register a table function that produces the items of an iterator.
+    ///
+    /// Every scan of the table function calls `make_iter` to create a fresh iterator, so the table can be queried
+    /// more than once.
+    ///
+    /// ```rust,no_run
+    /// # use duckdb::{core::{DataChunkHandle, LogicalTypeHandle}, vtab::{TableColumn, TableRow}, Connection, Result};
+    /// struct Planet {
+    ///     name: String,
+    ///     moons: i32,
+    /// }
+    ///
+    /// // with the `derive` feature this is `#[derive(TableRow)]`
+    /// impl TableRow for Planet {
+    ///     fn columns() -> Vec<(String, LogicalTypeHandle)> {
+    ///         vec![("name".to_string(), String::logical_type()), ("moons".to_string(), i32::logical_type())]
+    ///     }
+    ///
+    ///     fn write_to(self, output: &mut DataChunkHandle, row: usize) {
+    ///         self.name.write_to(&mut output.flat_vector(0), row);
+    ///         self.moons.write_to(&mut output.flat_vector(1), row);
+    ///     }
+    /// }
+    ///
+    /// fn register_planets(conn: &Connection) -> Result<()> {
+    ///     conn.register_iter_table("planets", || {
+    ///         [("Earth", 1), ("Mars", 2)].into_iter().map(|(name, moons)| Planet {
+    ///             name: name.to_string(),
+    ///             moons,
+    ///         })
+    ///     })
+    /// }
+    /// ```
+    pub fn register_iter_table<T, I, F>(&self, name: &str, make_iter: F) -> Result<()>
+    where
+        T: TableRow + 'static,
+        I: Iterator<Item = T> + Send + 'static,
+        F: Fn() -> I + Send + Sync + 'static,
+    {
+        let factory: Box<IterFactory<I>> = Box::new(Box::new(make_iter));
+        let table_function = table_function::<IterVTab<T, I>>(name);
+        unsafe {
+            table_function.set_extra_info(
+                Box::into_raw(factory) as *mut c_void,
+                Some(drop_boxed::<IterFactory<I>>),
+            );
+        }
+        self.db.borrow_mut().register_table_function(table_function)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{DataChunkHandle, LogicalTypeHandle},
+        vtab::{TableColumn, TableRow},
+        Connection,
+    };
+    use std::error::Error;
+
+    struct Person {
+        name: String,
+        age: Option<u8>,
+    }
+
+    impl TableRow for Person {
+        fn columns() -> Vec<(String, LogicalTypeHandle)> {
+            vec![
+                ("name".to_string(), String::logical_type()),
+                ("age".to_string(), Option::<u8>::logical_type()),
+            ]
+        }
+
+        fn write_to(self, output: &mut DataChunkHandle, row: usize) {
+            self.name.write_to(&mut output.flat_vector(0), row);
+            self.age.write_to(&mut output.flat_vector(1), row);
+        }
+    }
+
+    #[test]
+    fn test_iter_table() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_iter_table("people", || {
+            (0..5000).map(|i| Person {
+                name: format!("person {i}"),
+                age: (i % 10 != 0).then_some((i % 100) as u8),
+            })
+        })?;
+
+        let (count, ages, name): (i64, i64, String) =
+            conn.query_row("SELECT count(*), count(age), max(name) FROM people()", [], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?;
+        assert_eq!(count, 5000);
+        assert_eq!(ages, 4500);
+        assert_eq!(name, "person 999");
+
+        // every scan starts a new iterator
+        let joined: i64 = conn.query_row(
+            "SELECT count(*) FROM people() a JOIN people() b ON a.name = b.name",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(joined, 5000);
+        Ok(())
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_table_row() -> Result<(), Box<dyn Error>> {
+        #[derive(TableRow)]
+        struct Reading {
+            sensor: String,
+            value: f64,
+            flagged: Option<bool>,
+        }
+
+        let conn = Connection::open_in_memory()?;
+        conn.register_iter_table("readings", || {
+            ["a", "b", "c"].into_iter().enumerate().map(|(i, sensor)| Reading {
+                sensor: sensor.to_string(),
+                value: i as f64 * 1.5,
+                flagged: (i != 1).then_some(i == 0),
+            })
+        })?;
+
+        let mut stmt = conn.prepare("SELECT sensor, value, flagged FROM readings() ORDER BY sensor")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<crate::Result<Vec<(String, f64, Option<bool>)>>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                ("a".to_string(), 0.0, Some(true)),
+                ("b".to_string(), 1.5, None),
+                ("c".to_string(), 3.0, Some(false)),
+            ]
+        );
+        Ok(())
+    }
+}
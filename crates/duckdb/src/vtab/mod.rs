@@ -7,19 +7,29 @@ use crate::{error::Error, inner_connection::InnerConnection, Connection, Result}
 use super::ffi;
 
 mod function;
+mod iter;
 
 /// The duckdb Arrow table function interface
 #[cfg(feature = "vtab-arrow")]
 pub mod arrow;
 #[cfg(feature = "vtab-arrow")]
+mod arrow_table;
+#[cfg(feature = "vtab-arrow")]
 pub use self::arrow::{
-    arrow_arraydata_to_query_params, arrow_ffi_to_query_params, arrow_recordbatch_to_query_params,
-    record_batch_to_duckdb_data_chunk, to_duckdb_logical_type, to_duckdb_type_id,
+    array_to_duckdb_logical_type, arrow_arraydata_to_query_params, arrow_ffi_to_query_params,
+    arrow_recordbatch_to_query_params, record_batch_to_duckdb_data_chunk, to_duckdb_logical_type, to_duckdb_type_id,
+    VArrowTab,
 };
+#[cfg(feature = "vtab-arrow")]
+pub(crate) use self::arrow_table::ArrowTables;
 #[cfg(feature = "vtab-excel")]
 mod excel;
 
+/// Derive [`TableRow`] for a struct with named fields.
+#[cfg(feature = "derive")]
+pub use duckdb_loadable_macros::TableRow;
 pub use function::{BindInfo, InitInfo, TableFunction, TableFunctionInfo};
+pub use iter::{TableColumn, TableRow};
 
 use crate::core::{DataChunkHandle, LogicalTypeHandle};
 use ffi::{duckdb_bind_info, duckdb_data_chunk, duckdb_function_info, duckdb_init_info};
@@ -129,24 +139,29 @@ where
     }
 }
 
+/// Create a [TableFunction] that dispatches to the given [VTab]
+fn table_function<T: VTab>(name: &str) -> TableFunction {
+    let table_function = TableFunction::default();
+    table_function
+        .set_name(name)
+        .supports_pushdown(T::supports_pushdown())
+        .set_bind(Some(bind::<T>))
+        .set_init(Some(init::<T>))
+        .set_function(Some(func::<T>));
+    for ty in T::parameters().unwrap_or_default() {
+        table_function.add_parameter(&ty);
+    }
+    for (name, ty) in T::named_parameters().unwrap_or_default() {
+        table_function.add_named_parameter(&name, &ty);
+    }
+    table_function
+}
+
 impl Connection {
     /// Register the given TableFunction with the current db
     #[inline]
     pub fn register_table_function<T: VTab>(&self, name: &str) -> Result<()> {
-        let table_function = TableFunction::default();
-        table_function
-            .set_name(name)
-            .supports_pushdown(T::supports_pushdown())
-            .set_bind(Some(bind::<T>))
-            .set_init(Some(init::<T>))
-            .set_function(Some(func::<T>));
-        for ty in T::parameters().unwrap_or_default() {
-            table_function.add_parameter(&ty);
-        }
-        for (name, ty) in T::named_parameters().unwrap_or_default() {
-            table_function.add_named_parameter(&name, &ty);
-        }
-        self.db.borrow_mut().register_table_function(table_function)
+        self.db.borrow_mut().register_table_function(table_function::<T>(name))
     }
 }
 
@@ -166,7 +181,10 @@ impl InnerConnection {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::core::{Inserter, LogicalTypeId};
+    use crate::{
+        core::{FlatVector, Inserter, LogicalTypeId, Value},
+        ffi::duckdb_string_t,
+    };
     use std::{
         error::Error,
         ffi::{c_char, CString},
@@ -261,6 +279,142 @@ mod test {
         }
     }
 
+    struct NestedVTab;
+
+    impl VTab for NestedVTab {
+        type InitData = HelloInitData;
+        type BindData = ();
+
+        fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn Error>> {
+            let varchar = LogicalTypeHandle::from(LogicalTypeId::Varchar);
+            let integer = LogicalTypeHandle::from(LogicalTypeId::Integer);
+            bind.add_result_column("m", LogicalTypeHandle::map(&varchar, &integer));
+            bind.add_result_column(
+                "u",
+                LogicalTypeHandle::union_type(&[("num", integer), ("str", varchar)]),
+            );
+            Ok(())
+        }
+
+        fn init(init_info: &InitInfo) -> Result<Self::InitData, Box<dyn Error>> {
+            HelloVTab::init(init_info)
+        }
+
+        fn func(func: &TableFunctionInfo<Self>, output: &mut DataChunkHandle) -> Result<(), Box<dyn Error>> {
+            if func.get_init_data().done.swap(true, Ordering::Relaxed) {
+                output.set_len(0);
+                return Ok(());
+            }
+
+            let mut map = output.map_vector(0);
+            let offset = map.append_entry(0, 2);
+            let keys = map.keys(offset + 2);
+            keys.insert(offset, "a");
+            keys.insert(offset + 1, "b");
+            map.values(offset + 2).copy(&[1i32, 2]);
+            map.append_entry(1, 0);
+            map.set_null(1);
+            map.append_pairs::<duckdb_string_t, i32>(2, &[], &[]);
+
+            let mut union = output.union_vector(1);
+            union.set_tag(0, 0);
+            union.member(0, 3).copy(&[7i32]);
+            union.set_tag(1, 1);
+            union.member(1, 3).insert(1, "x");
+            union.set_null(2);
+
+            output.set_len(3);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_map_and_union_output() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<NestedVTab>("nested")?;
+
+        let mut stmt = conn.prepare("SELECT m::VARCHAR, u::VARCHAR, union_tag(u)::VARCHAR FROM nested()")?;
+        let rows = stmt
+            .query_map([], |row| {
+                <(Option<String>, Option<String>, Option<String>)>::try_from(row)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    Some("{a=1, b=2}".to_owned()),
+                    Some("7".to_owned()),
+                    Some("num".to_owned())
+                ),
+                (None, Some("x".to_owned()), Some("str".to_owned())),
+                (Some("{}".to_owned()), None, None),
+            ]
+        );
+        Ok(())
+    }
+
+    struct EncodedVTab;
+
+    impl VTab for EncodedVTab {
+        type InitData = HelloInitData;
+        type BindData = ();
+
+        fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn Error>> {
+            bind.add_result_column("color", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+            bind.add_result_column("answer", LogicalTypeHandle::from(LogicalTypeId::Integer));
+            Ok(())
+        }
+
+        fn init(init_info: &InitInfo) -> Result<Self::InitData, Box<dyn Error>> {
+            HelloVTab::init(init_info)
+        }
+
+        fn func(func: &TableFunctionInfo<Self>, output: &mut DataChunkHandle) -> Result<(), Box<dyn Error>> {
+            if func.get_init_data().done.swap(true, Ordering::Relaxed) {
+                output.set_len(0);
+                return Ok(());
+            }
+
+            let colors = ["red", "green", "blue"];
+            let mut dictionary =
+                FlatVector::allocate_new_vector_with_capacity(LogicalTypeHandle::from(LogicalTypeId::Varchar), 3);
+            for (idx, color) in colors.into_iter().enumerate() {
+                dictionary.insert(idx, color);
+            }
+            dictionary.set_null(2);
+            let mut color = output.flat_vector(0);
+            color
+                .set_dictionary(&dictionary, &[1, 0, 1, 2, 1])
+                .set_dictionary_id("colors");
+
+            output.flat_vector(1).assign_to_constant(&Value::from(42));
+            output.set_len(5);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_dictionary_and_constant_output() -> Result<(), Box<dyn Error>> {
+        let conn = Connection::open_in_memory()?;
+        conn.register_table_function::<EncodedVTab>("encoded")?;
+
+        let mut stmt = conn.prepare("SELECT color, answer FROM encoded()")?;
+        let rows = stmt
+            .query_map([], |row| <(Option<String>, i32)>::try_from(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let colors: Vec<_> = rows.iter().map(|(color, _)| color.as_deref()).collect();
+        assert_eq!(colors, [Some("green"), Some("red"), Some("green"), None, Some("green")]);
+        assert!(rows.iter().all(|(_, answer)| *answer == 42));
+
+        let counts: Vec<(String, i64)> = conn
+            .prepare("SELECT color, count(*) FROM encoded() WHERE color IS NOT NULL GROUP BY ALL ORDER BY ALL")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(counts, [("green".to_owned(), 3), ("red".to_owned(), 1)]);
+        Ok(())
+    }
+
     #[test]
     fn test_table_function() -> Result<(), Box<dyn Error>> {
         let conn = Connection::open_in_memory()?;